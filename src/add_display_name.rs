@@ -1,19 +1,51 @@
-use super::has_jsx::HasJSXVisitor;
+use super::config::{Config, Emit, Scope};
+use super::has_jsx::{is_hoc_name, member_path, HasJSXVisitor, LazyComponent};
+use super::imports::ImportMap;
 use std::collections::HashSet;
-use swc_core::common::{SyntaxContext, DUMMY_SP};
+use swc_core::common::{
+    comments::Comments, util::take::Take, BytePos, Mark, Span, Spanned, SyntaxContext, DUMMY_SP,
+};
 use swc_core::ecma::{
     ast::*,
     atoms::Atom,
-    visit::{VisitMut, VisitMutWith},
+    visit::{Visit, VisitMut, VisitMutWith, VisitWith},
 };
 
 struct Component {
     pos: usize,
     name: Atom,
     ctx: SyntaxContext,
+    /// Properties of compound components: `["Header"]` for `Card.Header`.
+    path: Vec<Atom>,
+    is_class: bool,
+    display_name: Box<Expr>,
 }
 
 impl Component {
+    fn new(ident: &Ident, is_class: bool) -> Component {
+        Component {
+            pos: 0,
+            name: ident.sym.clone(),
+            ctx: ident.ctxt,
+            path: vec![],
+            is_class,
+            display_name: str_lit(ident.sym.clone()),
+        }
+    }
+
+    /// A compound component like `Card.Header`, named with the dotted path.
+    fn member(root: &Ident, path: Vec<Atom>) -> Component {
+        let display_name = path
+            .iter()
+            .fold(root.sym.to_string(), |name, prop| format!("{name}.{prop}"));
+
+        Component {
+            path,
+            display_name: str_lit(display_name.into()),
+            ..Component::new(root, false)
+        }
+    }
+
     fn with_pos(self, pos: usize) -> Component {
         Component { pos, ..self }
     }
@@ -28,218 +60,1219 @@ impl Component {
                 op: AssignOp::Assign,
                 left: AssignTarget::Simple(SimpleAssignTarget::Member(MemberExpr {
                     span: DUMMY_SP,
-                    obj: Box::new(Expr::Ident(Ident::new(
-                        self.name.clone(),
-                        DUMMY_SP,
-                        self.ctx,
-                    ))),
+                    obj: self.target(),
                     prop: MemberProp::Ident(IdentName::new("displayName".into(), DUMMY_SP)),
                 })),
-                right: Box::new(Expr::Lit(Lit::Str(Str::from(self.name.clone())))),
+                right: self.display_name.clone(),
             })),
         })
     }
+
+    /// `Card` or `Card.Header`.
+    fn target(&self) -> Box<Expr> {
+        let root = Box::new(Expr::Ident(Ident::new(
+            self.name.clone(),
+            DUMMY_SP,
+            self.ctx,
+        )));
+
+        self.path.iter().fold(root, |obj, prop| {
+            Box::new(Expr::Member(MemberExpr {
+                span: DUMMY_SP,
+                obj,
+                prop: MemberProp::Ident(IdentName::new(prop.clone(), DUMMY_SP)),
+            }))
+        })
+    }
 }
 
-#[derive(Default)]
-pub struct AddDisplayNameVisitor;
+pub struct AddDisplayNameVisitor<C: Comments> {
+    config: Config,
+    filename: Option<String>,
+    comments: Option<C>,
+    imports: ImportMap,
+}
+
+impl<C: Comments> AddDisplayNameVisitor<C> {
+    pub fn new(config: Config, filename: Option<String>, comments: Option<C>) -> Self {
+        AddDisplayNameVisitor {
+            config,
+            filename,
+            comments,
+            imports: ImportMap::default(),
+        }
+    }
+}
+
+impl<C: Comments> VisitMut for AddDisplayNameVisitor<C> {
+    fn visit_mut_module(&mut self, node: &mut Module) {
+        self.imports = ImportMap::collect(&node.body);
+        self.read_jsx_pragmas(node.span, node.body.first().map(|item| item.span_lo()));
+        node.visit_mut_children_with(self);
+    }
 
-impl VisitMut for AddDisplayNameVisitor {
     fn visit_mut_module_items(&mut self, stmts: &mut Vec<ModuleItem>) {
         stmts.visit_mut_children_with(self);
+        self.name_default_export(stmts);
+        self.add_display_names(stmts, true);
+    }
+
+    fn visit_mut_script(&mut self, node: &mut Script) {
+        self.imports = ImportMap::collect_script(&node.body);
+        self.read_jsx_pragmas(node.span, node.body.first().map(|stmt| stmt.span_lo()));
+        node.body.visit_mut_children_with(self);
+        self.add_display_names(&mut node.body, true);
+    }
+
+    fn visit_mut_stmts(&mut self, stmts: &mut Vec<Stmt>) {
+        stmts.visit_mut_children_with(self);
+
+        if self.config.scope == Scope::All {
+            self.add_display_names(stmts, false);
+        }
+    }
+}
+
+impl<C: Comments> AddDisplayNameVisitor<C> {
+    /// Extends the JSX factories with the file's `/** @jsx h */` and
+    /// `/** @jsxImportSource preact */` pragmas, found before the first statement.
+    fn read_jsx_pragmas(&mut self, span: Span, first_stmt: Option<BytePos>) {
+        let Some(comments) = &self.comments else {
+            return;
+        };
+
+        let mut positions = vec![span.lo];
+        positions.extend(first_stmt);
+        positions.dedup();
+
+        for pos in positions {
+            let pragmas = comments.with_leading(pos, |comments| {
+                comments
+                    .iter()
+                    .flat_map(|comment| {
+                        let words: Vec<_> = comment
+                            .text
+                            .split_whitespace()
+                            .map(|word| word.trim_start_matches('*'))
+                            .filter(|word| !word.is_empty())
+                            .collect();
+                        words
+                            .windows(2)
+                            .map(|pair| (pair[0].to_string(), pair[1].to_string()))
+                            .collect::<Vec<_>>()
+                    })
+                    .collect::<Vec<_>>()
+            });
+
+            for (pragma, value) in pragmas {
+                match &*pragma {
+                    // `@jsx Preact.h` calls `Preact.h(...)`, matched by its last segment
+                    "@jsx" => {
+                        let name = value.rsplit('.').next().unwrap_or(&value).to_string();
+                        self.config.jsx_factories.push(name);
+                    }
+                    "@jsxImportSource" => self.config.jsx_import_sources.push(value),
+                    _ => {}
+                }
+            }
+        }
+    }
 
+    /// Names the components declared in a block. `top_level` is set for the
+    /// module or script body.
+    fn add_display_names<T: StmtLike>(&self, stmts: &mut Vec<T>, top_level: bool) {
         let mut components: Vec<Component> = Vec::new();
-        let mut components_names_with_display_name: HashSet<Atom> = HashSet::new();
+
+        // Existing assignments anywhere in the block count, e.g. inside `if (__DEV__) {}`.
+        // Bindings are compared by identity so that a shadowed binding with the
+        // same name doesn't count.
+        let mut collector = DisplayNameCollector::default();
+        stmts
+            .iter()
+            .for_each(|stmt| stmt.visit_with(&mut collector));
 
         stmts.iter_mut().enumerate().for_each(|(pos, stmt)| {
-            if let Some(var_decl) = extract_var_decl_from_module_item(stmt) {
+            if let Some(var_decl) = stmt.as_var_decl_mut() {
                 var_decl.decls.iter_mut().for_each(|var_declarator| {
-                    if let Some(comp) = process_var_declarator(var_declarator) {
-                        components.push(comp.with_pos(pos))
-                    }
+                    let mut comp =
+                        process_var_declarator(var_declarator, &self.config, &self.imports);
+
+                    let members = match (
+                        &var_declarator.name,
+                        var_declarator.init.as_deref_mut().map(unwrap_expr_mut),
+                    ) {
+                        // const Card = Object.assign(CardRoot, { Header })
+                        (Pat::Ident(binding), Some(Expr::Call(call_expr))) => {
+                            let members = process_object_assign(
+                                &binding.id,
+                                call_expr,
+                                &self.config,
+                                &self.imports,
+                            );
+                            // The root of a compound component is a component too
+                            if comp.is_none() && !members.is_empty() {
+                                comp = Some(Component::new(&binding.id, false));
+                            }
+                            members
+                        }
+                        // const Icons = { Add: () => <svg /> }
+                        (Pat::Ident(binding), Some(Expr::Object(object)))
+                            if self.config.object_literal_depth > 0 =>
+                        {
+                            process_object_literal(
+                                &binding.id,
+                                &[],
+                                object,
+                                self.config.object_literal_depth,
+                                &self.config,
+                                &self.imports,
+                            )
+                        }
+                        _ => vec![],
+                    };
+                    components.extend(
+                        comp.into_iter()
+                            .chain(members)
+                            .map(|comp| comp.with_pos(pos)),
+                    );
                 })
             }
 
-            if let Some(fn_decl) = extract_fn_decl_from_module_item(stmt) {
-                if let Some(comp) = process_fn_decl(fn_decl) {
+            if let Some(expr) = stmt.as_expr_mut() {
+                match expr {
+                    // exports.Button = () => <button />, Card.Header = () => <header />
+                    Expr::Assign(assign_expr) => {
+                        let comp = process_commonjs_export(
+                            assign_expr,
+                            self.filename.as_deref(),
+                            &self.config,
+                            &self.imports,
+                        )
+                        .or_else(|| {
+                            process_member_assignment(assign_expr, &self.config, &self.imports)
+                        });
+                        if let Some(comp) = comp {
+                            components.push(comp.with_pos(pos))
+                        }
+                    }
+                    // Object.assign(Card, { Header })
+                    Expr::Call(call_expr) => {
+                        if let Some(target) = call_expr
+                            .args
+                            .first()
+                            .and_then(|arg| arg.expr.as_ident())
+                            .cloned()
+                        {
+                            process_object_assign(&target, call_expr, &self.config, &self.imports)
+                                .into_iter()
+                                .for_each(|comp| components.push(comp.with_pos(pos)));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
+            if let Some(fn_decl) = stmt.as_fn_decl_mut() {
+                if let Some(comp) = process_fn_decl(fn_decl, &self.config, &self.imports) {
                     components.push(comp.with_pos(pos))
                 }
             }
 
-            if let Some(fn_expr) = extract_fn_expr_from_module_item(stmt) {
-                if let Some(comp) = process_fn_expr(fn_expr) {
+            if let Some(fn_expr) = stmt.as_default_fn_expr_mut() {
+                if let Some(comp) = process_fn_expr(fn_expr, &self.config, &self.imports) {
                     components.push(comp.with_pos(pos))
                 }
             }
 
-            if let Some(assign_expr) = extract_assignment_expr_from_module_item(stmt) {
-                if let Some(component_name) = process_assignment_expr(assign_expr) {
-                    components_names_with_display_name.insert(component_name);
+            if let Some(class_decl) = stmt.as_class_decl_mut() {
+                if let Some(comp) = process_class_decl(class_decl, &self.config, &self.imports) {
+                    components.push(comp.with_pos(pos))
+                }
+            }
+
+            if let Some(class_expr) = stmt.as_default_class_expr_mut() {
+                if let Some(comp) = process_class_expr(class_expr, &self.config, &self.imports) {
+                    components.push(comp.with_pos(pos))
                 }
             }
         });
 
-        components.iter().enumerate().for_each(|(i, comp)| {
-            let index = i + comp.pos + 1;
+        components.retain(|comp| {
+            !collector
+                .components_with_display_name
+                .contains(&((comp.name.clone(), comp.ctx), comp.path.clone()))
+        });
 
-            if components_names_with_display_name.contains(&comp.name) {
-                return;
-            }
+        // Class properties don't have side effects, so they suit `emit: "pure"` too
+        if self.config.static_display_name || self.config.emit == Emit::Pure {
+            components.retain(|comp| {
+                !comp.is_class || !add_static_display_name(&mut stmts[comp.pos], comp)
+            });
+        }
 
-            if index < stmts.len() {
-                stmts.insert(index, ModuleItem::Stmt(comp.create_display_name_stmt()));
-            } else {
-                stmts.push(ModuleItem::Stmt(comp.create_display_name_stmt()));
+        // Components that can't be wrapped in place (e.g. function declarations)
+        // fall back to assignments
+        if self.config.emit == Emit::Pure {
+            components.retain(|comp| !self.wrap_initializer(&mut stmts[comp.pos], comp));
+        }
+
+        match &self.config.dev_guard {
+            // One guarded block at the end of the module. Nested blocks may end
+            // with a `return`, so they are guarded per component.
+            Some(guard) if guard.batch && top_level => {
+                if !components.is_empty() {
+                    let body = components
+                        .iter()
+                        .map(Component::create_display_name_stmt)
+                        .collect();
+                    stmts.push(T::from_stmt(create_guard_stmt(&guard.condition.0, body)));
+                }
             }
-        })
+
+            // One assignment right after each component, guarded individually if asked to.
+            // Inserting backwards keeps the positions of the remaining components valid.
+            guard => components.iter().rev().for_each(|comp| {
+                let stmt = match guard {
+                    Some(guard) => {
+                        create_guard_stmt(&guard.condition.0, vec![comp.create_display_name_stmt()])
+                    }
+                    None => comp.create_display_name_stmt(),
+                };
+                stmts.insert(comp.pos + 1, T::from_stmt(stmt));
+            }),
+        }
     }
+}
 
-    fn visit_mut_script(&mut self, node: &mut Script) {
-        node.body.visit_mut_children_with(self);
+impl<C: Comments> AddDisplayNameVisitor<C> {
+    /// Names an anonymous default-exported component after the file, so that
+    /// `export default () => <div />` in `UserCard.tsx` becomes
+    /// `const UserCard = () => <div />; export default UserCard;`.
+    ///
+    /// Wrapped components like `export default memo(function Card() {})` are
+    /// hoisted the same way, named after the inner function when it has a name.
+    /// TypeScript's `export = function Layout() {}` is handled like a default export.
+    fn name_default_export(&self, items: &mut Vec<ModuleItem>) {
+        let filename_name = self
+            .filename
+            .as_deref()
+            .and_then(component_name_from_filename);
 
-        let mut components: Vec<Component> = Vec::new();
-        let mut components_names_with_display_name: HashSet<Atom> = HashSet::new();
+        let Some(pos) = items.iter().position(|item| {
+            matches!(
+                item,
+                ModuleItem::ModuleDecl(
+                    ModuleDecl::ExportDefaultDecl(_)
+                        | ModuleDecl::ExportDefaultExpr(_)
+                        | ModuleDecl::TsExportAssignment(_)
+                )
+            )
+        }) else {
+            return;
+        };
 
-        node.body.iter_mut().enumerate().for_each(|(pos, stmt)| {
-            if let Some(var_decl) = extract_var_decl_from_stmt(stmt) {
-                var_decl.decls.iter_mut().for_each(|var_declarator| {
-                    if let Some(comp) = process_var_declarator(var_declarator) {
-                        components.push(comp.with_pos(pos))
+        let hoisted_name = match &mut items[pos] {
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+                decl,
+                ..
+            })) => {
+                // `export default function () {}` only needs a name to become a declaration
+                match (decl, filename_name) {
+                    (DefaultDecl::Fn(fn_expr), Some(name)) if fn_expr.ident.is_none() => {
+                        if HasJSXVisitor::test(fn_expr, &self.config, &self.imports) {
+                            fn_expr.ident = Some(private_ident(name));
+                        }
                     }
-                })
-            }
-
-            if let Some(fn_decl) = extract_fn_decl_from_stmt(stmt) {
-                if let Some(comp) = process_fn_decl(fn_decl) {
-                    components.push(comp.with_pos(pos))
+                    (DefaultDecl::Class(class_expr), Some(name)) if class_expr.ident.is_none() => {
+                        if HasJSXVisitor::test(class_expr, &self.config, &self.imports) {
+                            class_expr.ident = Some(private_ident(name));
+                        }
+                    }
+                    _ => {}
                 }
+                None
             }
-
-            if let Some(assign_expr) = extract_assignment_expr_from_stmt(stmt) {
-                if let Some(component_name) = process_assignment_expr(assign_expr) {
-                    components_names_with_display_name.insert(component_name);
-                }
+            ModuleItem::ModuleDecl(
+                ModuleDecl::ExportDefaultExpr(ExportDefaultExpr { expr, .. })
+                | ModuleDecl::TsExportAssignment(TsExportAssignment { expr, .. }),
+            ) => {
+                let name = match unwrap_expr(expr) {
+                    Expr::Arrow(_)
+                    | Expr::Fn(FnExpr { ident: None, .. })
+                    | Expr::Class(ClassExpr { ident: None, .. }) => filename_name,
+                    // `export = function Layout() {}` can't be referenced by its name
+                    Expr::Fn(FnExpr {
+                        ident: Some(ident), ..
+                    })
+                    | Expr::Class(ClassExpr {
+                        ident: Some(ident), ..
+                    }) => Some(ident.sym.clone()),
+                    Expr::Call(call_expr) => match wrapped_expr(call_expr) {
+                        Some(Expr::Fn(FnExpr {
+                            ident: Some(ident), ..
+                        }))
+                        | Some(Expr::Class(ClassExpr {
+                            ident: Some(ident), ..
+                        })) => Some(ident.sym.clone()),
+                        // The wrapped binding already owns its name, prefer the file name
+                        Some(Expr::Ident(ident)) => {
+                            filename_name.or_else(|| Some(ident.sym.clone()))
+                        }
+                        _ => filename_name,
+                    },
+                    _ => None,
+                };
+                name.filter(|_| HasJSXVisitor::test(&mut **expr, &self.config, &self.imports))
             }
+            _ => None,
+        };
+
+        if let Some(name) = hoisted_name {
+            hoist_default_export(items, pos, private_ident(name));
+        }
+    }
+
+    /// Rewrites `const X = init` into
+    /// `const X = /*#__PURE__*/ Object.assign(init, { displayName: "X" })`,
+    /// which bundlers may drop when `X` is unused.
+    fn wrap_initializer<T: StmtLike>(&self, stmt: &mut T, comp: &Component) -> bool {
+        let Some(init) = find_var_declarator_mut(stmt, comp)
+            .and_then(|var_declarator| var_declarator.init.as_mut())
+        else {
+            return false;
+        };
+
+        // The call gets a position of its own, so that an annotation on the
+        // initializer, like `/*#__PURE__*/ memo(...)`, stays where it is
+        let span = Span::dummy_with_cmt();
+        if let Some(comments) = &self.comments {
+            comments.add_pure_comment(span.lo);
+        }
+
+        let props = Expr::Object(ObjectLit {
+            span: DUMMY_SP,
+            props: vec![PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                key: PropName::Ident(IdentName::new("displayName".into(), DUMMY_SP)),
+                value: comp.display_name.clone(),
+            })))],
         });
+        let props = match &self.config.dev_guard {
+            Some(guard) => Expr::Cond(CondExpr {
+                span: DUMMY_SP,
+                test: guard.condition.0.clone(),
+                cons: Box::new(props),
+                alt: Box::new(Expr::Object(ObjectLit {
+                    span: DUMMY_SP,
+                    props: vec![],
+                })),
+            }),
+            None => props,
+        };
 
-        components.iter().enumerate().for_each(|(i, comp)| {
-            let index = i + comp.pos + 1;
+        let target = init.take();
+        **init = Expr::Call(CallExpr {
+            span,
+            ctxt: SyntaxContext::empty(),
+            callee: Callee::Expr(Box::new(Expr::Member(MemberExpr {
+                span: DUMMY_SP,
+                obj: Box::new(Expr::Ident(Ident::new(
+                    "Object".into(),
+                    DUMMY_SP,
+                    SyntaxContext::empty(),
+                ))),
+                prop: MemberProp::Ident(IdentName::new("assign".into(), DUMMY_SP)),
+            }))),
+            args: vec![target.into(), props.into()],
+            type_args: None,
+        });
 
-            if components_names_with_display_name.contains(&comp.name) {
-                return;
-            }
+        true
+    }
+}
 
-            if index < node.body.len() {
-                node.body.insert(index, comp.create_display_name_stmt());
-            } else {
-                node.body.push(comp.create_display_name_stmt());
-            }
+/// Replaces `export default expr` by `const X = expr; export default X;`.
+fn hoist_default_export(items: &mut Vec<ModuleItem>, pos: usize, ident: Ident) {
+    let ModuleItem::ModuleDecl(
+        ModuleDecl::ExportDefaultExpr(ExportDefaultExpr { expr, .. })
+        | ModuleDecl::TsExportAssignment(TsExportAssignment { expr, .. }),
+    ) = &mut items[pos]
+    else {
+        return;
+    };
+
+    let mut init = expr.take();
+    while init.is_paren() {
+        init = (*init).expect_paren().expr;
+    }
+    **expr = Expr::Ident(ident.clone());
+
+    items.insert(
+        pos,
+        ModuleItem::Stmt(Stmt::Decl(Decl::Var(Box::new(VarDecl {
+            span: DUMMY_SP,
+            ctxt: SyntaxContext::empty(),
+            kind: VarDeclKind::Const,
+            declare: false,
+            decls: vec![VarDeclarator {
+                span: DUMMY_SP,
+                name: Pat::Ident(ident.into()),
+                init: Some(init),
+                definite: false,
+            }],
+        })))),
+    );
+}
+
+/// Creates a binding that can't clash with the existing ones. Hygiene renames
+/// it if the name is already taken.
+fn private_ident(name: Atom) -> Ident {
+    Ident::new(
+        name,
+        DUMMY_SP,
+        SyntaxContext::empty().apply_mark(Mark::new()),
+    )
+}
+
+/// Derives a component name from a file name: `UserCard.tsx` and
+/// `user-card/index.tsx` both give `UserCard`.
+fn component_name_from_filename(filename: &str) -> Option<Atom> {
+    let filename = filename.replace('\\', "/");
+    let mut segments = filename.rsplit('/').filter(|segment| !segment.is_empty());
+
+    let stem = segments.next()?.split('.').next()?;
+    let stem = if stem == "index" {
+        segments.next()?
+    } else {
+        stem
+    };
+
+    let name: String = stem
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .flat_map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase())
+                .into_iter()
+                .chain(chars)
+        })
+        .collect();
+
+    match name.chars().next() {
+        None => None,
+        Some(first) if first.is_ascii_digit() => Some(format!("_{name}").into()),
+        Some(_) => Some(name.into()),
+    }
+}
+
+/// Finds what a wrapper call wraps: the function in `memo(function Card() {})`,
+/// or `Profile` in `connect(mapState)(Profile)`.
+fn wrapped_expr(call_expr: &CallExpr) -> Option<&Expr> {
+    match unwrap_expr(&call_expr.args.first()?.expr) {
+        Expr::Call(inner) => wrapped_expr(inner),
+        arg => Some(arg),
+    }
+}
+
+/// Looks through parentheses and TypeScript wrappers: `(() => <div />)`,
+/// `forwardRef(...) as ButtonComponent`, `(() => <div />) satisfies FC`, `<FC>(...)`.
+pub fn unwrap_expr(expr: &Expr) -> &Expr {
+    match expr {
+        Expr::Paren(ParenExpr { expr, .. })
+        | Expr::TsAs(TsAsExpr { expr, .. })
+        | Expr::TsSatisfies(TsSatisfiesExpr { expr, .. })
+        | Expr::TsNonNull(TsNonNullExpr { expr, .. })
+        | Expr::TsTypeAssertion(TsTypeAssertion { expr, .. })
+        | Expr::TsConstAssertion(TsConstAssertion { expr, .. }) => unwrap_expr(expr),
+        _ => expr,
+    }
+}
+
+fn unwrap_expr_mut(expr: &mut Expr) -> &mut Expr {
+    match expr {
+        Expr::Paren(ParenExpr { expr, .. })
+        | Expr::TsAs(TsAsExpr { expr, .. })
+        | Expr::TsSatisfies(TsSatisfiesExpr { expr, .. })
+        | Expr::TsNonNull(TsNonNullExpr { expr, .. })
+        | Expr::TsTypeAssertion(TsTypeAssertion { expr, .. })
+        | Expr::TsConstAssertion(TsConstAssertion { expr, .. }) => unwrap_expr_mut(expr),
+        _ => expr,
+    }
+}
+
+fn find_var_declarator_mut<'a, T: StmtLike>(
+    stmt: &'a mut T,
+    comp: &Component,
+) -> Option<&'a mut VarDeclarator> {
+    // `Card.Header` isn't declared, even when `Card` is declared in the same statement
+    if !comp.path.is_empty() {
+        return None;
+    }
+
+    stmt.as_var_decl_mut()?
+        .decls
+        .iter_mut()
+        .find(|var_declarator| {
+            var_declarator
+                .name
+                .as_ident()
+                .is_some_and(|binding| binding.id.sym == comp.name && binding.id.ctxt == comp.ctx)
+        })
+}
+
+fn find_class_mut<'a, T: StmtLike>(stmt: &'a mut T, comp: &Component) -> Option<&'a mut Class> {
+    if stmt.as_class_decl_mut().is_some() {
+        return stmt
+            .as_class_decl_mut()
+            .map(|class_decl| &mut *class_decl.class);
+    }
+
+    if stmt.as_default_class_expr_mut().is_some() {
+        return stmt
+            .as_default_class_expr_mut()
+            .map(|class_expr| &mut *class_expr.class);
+    }
+
+    match unwrap_expr_mut(find_var_declarator_mut(stmt, comp)?.init.as_deref_mut()?) {
+        Expr::Class(class_expr) => Some(&mut *class_expr.class),
+        _ => None,
+    }
+}
+
+/// Adds `static displayName = "X";` to the class body.
+fn add_static_display_name<T: StmtLike>(stmt: &mut T, comp: &Component) -> bool {
+    let Some(class) = find_class_mut(stmt, comp) else {
+        return false;
+    };
+
+    class.body.insert(
+        0,
+        ClassMember::ClassProp(ClassProp {
+            span: DUMMY_SP,
+            key: PropName::Ident(IdentName::new("displayName".into(), DUMMY_SP)),
+            value: Some(comp.display_name.clone()),
+            type_ann: None,
+            is_static: true,
+            decorators: vec![],
+            accessibility: None,
+            is_abstract: false,
+            is_optional: false,
+            is_override: false,
+            readonly: false,
+            declare: false,
+            definite: false,
+        }),
+    );
+
+    true
+}
+
+fn has_static_display_name(class: &Class) -> bool {
+    class.body.iter().any(|member| match member {
+        ClassMember::ClassProp(ClassProp {
+            key: PropName::Ident(key),
+            is_static: true,
+            ..
         })
+        | ClassMember::Method(ClassMethod {
+            key: PropName::Ident(key),
+            is_static: true,
+            ..
+        }) => &*key.sym == "displayName",
+        _ => false,
+    })
+}
+
+fn create_guard_stmt(test: &Expr, stmts: Vec<Stmt>) -> Stmt {
+    Stmt::If(IfStmt {
+        span: DUMMY_SP,
+        test: Box::new(test.clone()),
+        cons: Box::new(Stmt::Block(BlockStmt {
+            span: DUMMY_SP,
+            ctxt: SyntaxContext::empty(),
+            stmts,
+        })),
+        alt: None,
+    })
+}
+
+/// Lets the same logic run over module items and script statements.
+trait StmtLike: Sized + VisitWith<DisplayNameCollector> {
+    fn from_stmt(stmt: Stmt) -> Self;
+    fn as_var_decl_mut(&mut self) -> Option<&mut VarDecl>;
+    fn as_fn_decl_mut(&mut self) -> Option<&mut FnDecl>;
+    fn as_default_fn_expr_mut(&mut self) -> Option<&mut FnExpr>;
+    fn as_class_decl_mut(&mut self) -> Option<&mut ClassDecl>;
+    fn as_default_class_expr_mut(&mut self) -> Option<&mut ClassExpr>;
+    fn as_expr_mut(&mut self) -> Option<&mut Expr>;
+}
+
+impl StmtLike for ModuleItem {
+    fn from_stmt(stmt: Stmt) -> Self {
+        ModuleItem::Stmt(stmt)
+    }
+
+    fn as_var_decl_mut(&mut self) -> Option<&mut VarDecl> {
+        extract_var_decl_from_module_item(self)
+    }
+
+    fn as_fn_decl_mut(&mut self) -> Option<&mut FnDecl> {
+        extract_fn_decl_from_module_item(self)
+    }
+
+    fn as_default_fn_expr_mut(&mut self) -> Option<&mut FnExpr> {
+        extract_fn_expr_from_module_item(self)
+    }
+
+    fn as_class_decl_mut(&mut self) -> Option<&mut ClassDecl> {
+        extract_class_decl_from_module_item(self)
+    }
+
+    fn as_default_class_expr_mut(&mut self) -> Option<&mut ClassExpr> {
+        extract_class_expr_from_module_item(self)
+    }
+
+    fn as_expr_mut(&mut self) -> Option<&mut Expr> {
+        match self {
+            ModuleItem::Stmt(stmt) => extract_expr_from_stmt(stmt),
+            _ => None,
+        }
+    }
+}
+
+impl StmtLike for Stmt {
+    fn from_stmt(stmt: Stmt) -> Self {
+        stmt
+    }
+
+    fn as_var_decl_mut(&mut self) -> Option<&mut VarDecl> {
+        extract_var_decl_from_stmt(self)
+    }
+
+    fn as_fn_decl_mut(&mut self) -> Option<&mut FnDecl> {
+        extract_fn_decl_from_stmt(self)
+    }
+
+    fn as_default_fn_expr_mut(&mut self) -> Option<&mut FnExpr> {
+        None
+    }
+
+    fn as_class_decl_mut(&mut self) -> Option<&mut ClassDecl> {
+        extract_class_decl_from_stmt(self)
+    }
+
+    fn as_default_class_expr_mut(&mut self) -> Option<&mut ClassExpr> {
+        None
+    }
+
+    fn as_expr_mut(&mut self) -> Option<&mut Expr> {
+        extract_expr_from_stmt(self)
     }
 }
 
 fn extract_var_decl_from_module_item(stmt: &mut ModuleItem) -> Option<&mut VarDecl> {
     match stmt {
-        ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { span: _, decl })) => {
-            match decl {
-                Decl::Var(var_decl) => Some(var_decl),
-                _ => None,
-            }
-        }
-        ModuleItem::Stmt(Stmt::Decl(Decl::Var(var_decl))) => Some(var_decl),
+        ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+            decl: Decl::Var(var_decl),
+            ..
+        })) if !var_decl.declare => Some(var_decl),
+        ModuleItem::Stmt(Stmt::Decl(Decl::Var(var_decl))) if !var_decl.declare => Some(var_decl),
         _ => None,
     }
 }
 
-fn process_var_declarator(var_decl: &mut VarDeclarator) -> Option<Component> {
-    // Skip existing displayNames and obvious non-components
-    if let Some(init) = &var_decl.init {
-        if init.is_jsx_element() || init.is_jsx_fragment() || init.is_paren() || init.is_object() {
+fn process_var_declarator(
+    var_decl: &mut VarDeclarator,
+    config: &Config,
+    imports: &ImportMap,
+) -> Option<Component> {
+    // Skip obvious non-components, looking through `(...)`, `as`, `satisfies`, ...
+    if let Some(init) = var_decl.init.as_deref().map(unwrap_expr) {
+        if init.is_jsx_element() || init.is_jsx_fragment() || init.is_object() {
             return None;
         }
     }
 
-    // Check for component indicators (JSX OR API calls)
-    let has_component_indicators = HasJSXVisitor::test(var_decl);
+    // With HOC naming, `withStyles(styles)(Button)` counts as a component as well
+    let hoc = match var_decl.init.as_deref() {
+        Some(init) if config.hoc_display_name => {
+            hoc_chain(init, config, imports).map(|(wrappers, inner)| {
+                let wraps_component = is_pascal_case(&inner.sym);
+                (wraps_component, hoc_display_name(&wrappers, inner, imports))
+            })
+        }
+        _ => None,
+    };
+
+    // `const Empty: React.FC = () => null`, but not `let Current: React.FC;`
+    let has_component_type = var_decl.init.is_some()
+        && var_decl
+            .name
+            .as_ident()
+            .and_then(|binding| binding.type_ann.as_deref())
+            .is_some_and(|type_ann| HasJSXVisitor::is_component_type_ann(type_ann, config));
+
+    // Check for component indicators (JSX OR API calls OR types)
+    let has_component_indicators = has_component_type
+        || HasJSXVisitor::test(var_decl, config, imports)
+        || hoc
+            .as_ref()
+            .is_some_and(|(wraps_component, _)| *wraps_component);
     if !has_component_indicators {
         return None;
     };
 
+    let is_class = match var_decl.init.as_deref().map(unwrap_expr) {
+        Some(Expr::Class(class_expr)) => {
+            if has_static_display_name(&class_expr.class) {
+                return None;
+            }
+            true
+        }
+        _ => false,
+    };
+
     let name = &var_decl.name.as_ident()?.id;
-    Some(Component {
-        pos: 0,
-        name: name.sym.clone(),
-        ctx: name.ctxt,
-    })
+    let mut comp = Component::new(name, is_class);
+
+    if let Some((_, display_name)) = hoc {
+        comp.display_name = display_name;
+    }
+
+    if let Some(template) = &config.lazy_display_name {
+        let lazy = var_decl
+            .init
+            .as_deref()
+            .and_then(|init| HasJSXVisitor::lazy_component(unwrap_expr(init), config, imports));
+        if let Some(lazy) = lazy {
+            comp.display_name = str_lit(format_lazy_display_name(template, &comp.name, &lazy));
+        }
+    }
+
+    Some(comp)
+}
+
+/// Unrolls higher-order component calls, so that `withRouter(connect(mapState)(Profile))`
+/// gives `(["withRouter", "Connect"], Profile)`.
+fn hoc_chain<'a>(
+    expr: &'a Expr,
+    config: &Config,
+    imports: &ImportMap,
+) -> Option<(Vec<Atom>, &'a Ident)> {
+    let Expr::Call(call_expr) = unwrap_expr(expr) else {
+        return None;
+    };
+    let Callee::Expr(callee) = &call_expr.callee else {
+        return None;
+    };
+
+    let hoc = match unwrap_expr(callee) {
+        // connect(mapState)(Profile), withStyles(styles)(Button)
+        Expr::Call(CallExpr {
+            callee: Callee::Expr(callee),
+            ..
+        }) => hoc_name(callee, config, imports)?,
+        // withRouter(Profile)
+        callee => hoc_name(callee, config, imports).filter(|name| name.starts_with("with"))?,
+    };
+
+    let (mut wrappers, inner) = match unwrap_expr(&call_expr.args.first()?.expr) {
+        Expr::Ident(ident) => (vec![], ident),
+        arg => hoc_chain(arg, config, imports)?,
+    };
+    wrappers.insert(0, hoc);
+
+    Some((wrappers, inner))
+}
+
+/// `connect` is shown as `Connect` by convention, `withXxx` keep their names.
+///
+/// Only imported HOCs count, and `connect` only from a component source, so that
+/// e.g. `connect(url)(Config)` from a database client is left alone.
+fn hoc_name(callee: &Expr, config: &Config, imports: &ImportMap) -> Option<Atom> {
+    let (import, name) = match callee {
+        Expr::Ident(ident) => (imports.get(ident)?, imports.imported_name(ident)),
+        Expr::Member(MemberExpr {
+            obj,
+            prop: MemberProp::Ident(prop),
+            ..
+        }) => (imports.get(obj.as_ident()?)?, &prop.sym),
+        _ => return None,
+    };
+
+    match &**name {
+        "connect" if config.is_component_source(&import.src) => Some("Connect".into()),
+        name if is_hoc_name(name) => Some(name.into()),
+        _ => None,
+    }
+}
+
+/// Builds `"Connect(Profile)"`. When `Profile` is imported its name is only
+/// known at runtime: `"Connect(" + (Profile.displayName || Profile.name || "Component") + ")"`.
+fn hoc_display_name(wrappers: &[Atom], inner: &Ident, imports: &ImportMap) -> Box<Expr> {
+    let prefix: String = wrappers
+        .iter()
+        .map(|wrapper| format!("{wrapper}("))
+        .collect();
+    let suffix = ")".repeat(wrappers.len());
+
+    if imports.get(inner).is_none() {
+        return str_lit(format!("{prefix}{}{suffix}", inner.sym).into());
+    }
+
+    let member = |prop: &str| {
+        Box::new(Expr::Member(MemberExpr {
+            span: DUMMY_SP,
+            obj: Box::new(Expr::Ident(inner.clone())),
+            prop: MemberProp::Ident(IdentName::new(prop.into(), DUMMY_SP)),
+        }))
+    };
+    let binary = |op, left, right| {
+        Box::new(Expr::Bin(BinExpr {
+            span: DUMMY_SP,
+            op,
+            left,
+            right,
+        }))
+    };
+
+    let inner_name = binary(
+        BinaryOp::LogicalOr,
+        binary(BinaryOp::LogicalOr, member("displayName"), member("name")),
+        str_lit("Component".into()),
+    );
+    let inner_name = Box::new(Expr::Paren(ParenExpr {
+        span: DUMMY_SP,
+        expr: inner_name,
+    }));
+
+    binary(
+        BinaryOp::Add,
+        binary(BinaryOp::Add, str_lit(prefix.into()), inner_name),
+        str_lit(suffix.into()),
+    )
+}
+
+fn str_lit(value: Atom) -> Box<Expr> {
+    Box::new(Expr::Lit(Lit::Str(Str::from(value))))
+}
+
+/// Fills in the `{name}`, `{kind}` and `{path}` placeholders of the `lazyDisplayName` template.
+///
+/// `{path}` falls back to the variable name when there's no literal `import("...")`,
+/// as in `lazy(load)`.
+fn format_lazy_display_name(template: &str, name: &str, lazy: &LazyComponent) -> Atom {
+    template
+        .replace("{name}", name)
+        .replace("{kind}", lazy.kind)
+        .replace("{path}", lazy.path.as_deref().unwrap_or(name))
+        .into()
 }
 
 fn extract_fn_expr_from_module_item(stmt: &mut ModuleItem) -> Option<&mut FnExpr> {
     match stmt {
         ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
-            span: _,
-            decl,
-        })) => match decl {
-            DefaultDecl::Fn(fn_expr) => Some(fn_expr),
-            _ => None,
-        },
+            decl: DefaultDecl::Fn(fn_expr),
+            ..
+        })) => Some(fn_expr),
         _ => None,
     }
 }
 
-fn process_fn_expr(fn_expr: &mut FnExpr) -> Option<Component> {
-    let has_jsx = HasJSXVisitor::test(fn_expr);
-    if !has_jsx {
+fn process_fn_expr(
+    fn_expr: &mut FnExpr,
+    config: &Config,
+    imports: &ImportMap,
+) -> Option<Component> {
+    let is_component = HasJSXVisitor::test(fn_expr, config, imports);
+    if !is_component {
         return None;
     };
 
     if let Some(name) = &fn_expr.ident {
-        return Some(Component {
-            pos: 0,
-            name: name.sym.clone(),
-            ctx: name.ctxt,
-        });
+        return Some(Component::new(name, false));
     }
-    return None;
+    None
 }
 
 fn extract_fn_decl_from_module_item(stmt: &mut ModuleItem) -> Option<&mut FnDecl> {
     match stmt {
-        ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { span: _, decl })) => {
-            match decl {
-                Decl::Fn(fn_decl) => Some(fn_decl),
-                _ => None,
-            }
-        }
-        ModuleItem::Stmt(Stmt::Decl(Decl::Fn(fn_decl))) => Some(fn_decl),
+        ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+            decl: Decl::Fn(fn_decl),
+            ..
+        })) if !fn_decl.declare => Some(fn_decl),
+        ModuleItem::Stmt(Stmt::Decl(Decl::Fn(fn_decl))) if !fn_decl.declare => Some(fn_decl),
         _ => None,
     }
 }
 
-fn process_fn_decl(fn_decl: &mut FnDecl) -> Option<Component> {
-    let has_jsx = HasJSXVisitor::test(fn_decl);
-    if !has_jsx {
+fn process_fn_decl(
+    fn_decl: &mut FnDecl,
+    config: &Config,
+    imports: &ImportMap,
+) -> Option<Component> {
+    let is_component = HasJSXVisitor::test(fn_decl, config, imports);
+    if !is_component {
         return None;
     };
 
     let name = &fn_decl.ident;
-    Some(Component {
-        pos: 0,
-        name: name.sym.clone(),
-        ctx: name.ctxt,
-    })
+    Some(Component::new(name, false))
 }
 
-fn extract_assignment_expr_from_module_item(stmt: &mut ModuleItem) -> Option<&mut AssignExpr> {
+fn extract_class_decl_from_module_item(stmt: &mut ModuleItem) -> Option<&mut ClassDecl> {
     match stmt {
-        ModuleItem::Stmt(Stmt::Expr(ExprStmt { expr, .. })) => match &mut **expr {
-            Expr::Assign(assign_expr) => Some(assign_expr),
-            _ => None,
-        },
+        ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+            decl: Decl::Class(class_decl),
+            ..
+        })) if !class_decl.declare => Some(class_decl),
+        ModuleItem::Stmt(Stmt::Decl(Decl::Class(class_decl))) if !class_decl.declare => {
+            Some(class_decl)
+        }
         _ => None,
     }
 }
 
-fn process_assignment_expr(expr: &mut AssignExpr) -> Option<Atom> {
+fn process_class_decl(
+    class_decl: &mut ClassDecl,
+    config: &Config,
+    imports: &ImportMap,
+) -> Option<Component> {
+    if has_static_display_name(&class_decl.class) {
+        return None;
+    }
+
+    let is_component = HasJSXVisitor::test(class_decl, config, imports);
+    if !is_component {
+        return None;
+    };
+
+    let name = &class_decl.ident;
+    Some(Component::new(name, true))
+}
+
+fn extract_class_expr_from_module_item(stmt: &mut ModuleItem) -> Option<&mut ClassExpr> {
+    match stmt {
+        ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+            decl: DefaultDecl::Class(class_expr),
+            ..
+        })) => Some(class_expr),
+        _ => None,
+    }
+}
+
+fn process_class_expr(
+    class_expr: &mut ClassExpr,
+    config: &Config,
+    imports: &ImportMap,
+) -> Option<Component> {
+    if has_static_display_name(&class_expr.class) {
+        return None;
+    }
+
+    let is_component = HasJSXVisitor::test(class_expr, config, imports);
+    if !is_component {
+        return None;
+    };
+
+    let name = class_expr.ident.as_ref()?;
+    Some(Component::new(name, true))
+}
+
+/// CommonJS exports: `exports.Button = ...` and `module.exports.Button = ...` are
+/// named after the property, `module.exports = function Layout() {}` after the function.
+fn process_commonjs_export(
+    assign_expr: &mut AssignExpr,
+    filename: Option<&str>,
+    config: &Config,
+    imports: &ImportMap,
+) -> Option<Component> {
+    if assign_expr.op != AssignOp::Assign {
+        return None;
+    }
+    let AssignTarget::Simple(SimpleAssignTarget::Member(MemberExpr {
+        obj,
+        prop: MemberProp::Ident(prop),
+        ..
+    })) = &assign_expr.left
+    else {
+        return None;
+    };
+
+    let (root, mut path) = member_path(obj)?;
+    path.push(prop.sym.clone());
+
+    let name = match path.iter().map(|segment| &**segment).collect::<Vec<_>>()[..] {
+        // Default exports are named after the function, or after the file like
+        // `export default () => <div />`
+        ["module", "exports"] | ["exports", "default"] | ["module", "exports", "default"] => {
+            match unwrap_expr(&assign_expr.right) {
+                Expr::Fn(FnExpr {
+                    ident: Some(ident), ..
+                })
+                | Expr::Class(ClassExpr {
+                    ident: Some(ident), ..
+                }) => ident.sym.clone(),
+                _ => filename.and_then(component_name_from_filename)?,
+            }
+        }
+        ["exports", name] | ["module", "exports", name] if name != "displayName" => name.into(),
+        _ => return None,
+    };
+    let comp = Component {
+        display_name: str_lit(name),
+        ..Component::member(root, path[1..].to_vec())
+    };
+
+    HasJSXVisitor::test(&mut *assign_expr.right, config, imports).then_some(comp)
+}
+
+/// Compound components: `Card.Header = () => <header />` is named `Card.Header`.
+fn process_member_assignment(
+    assign_expr: &mut AssignExpr,
+    config: &Config,
+    imports: &ImportMap,
+) -> Option<Component> {
+    if assign_expr.op != AssignOp::Assign {
+        return None;
+    }
+    let AssignTarget::Simple(SimpleAssignTarget::Member(MemberExpr {
+        obj,
+        prop: MemberProp::Ident(prop),
+        ..
+    })) = &assign_expr.left
+    else {
+        return None;
+    };
+
+    let (root, mut path) = member_path(obj)?;
+    path.push(prop.sym.clone());
+    if !path.iter().all(|segment| is_pascal_case(segment)) {
+        return None;
+    }
+    let comp = Component::member(root, path[1..].to_vec());
+
+    HasJSXVisitor::test(&mut *assign_expr.right, config, imports).then_some(comp)
+}
+
+/// `Object.assign(Card, { Header, Body: CardBody })` names `Card.Header` and `Card.Body`.
+fn process_object_assign(
+    target: &Ident,
+    call_expr: &mut CallExpr,
+    config: &Config,
+    imports: &ImportMap,
+) -> Vec<Component> {
+    let is_object_assign = match &call_expr.callee {
+        Callee::Expr(callee) => match &**callee {
+            Expr::Member(MemberExpr {
+                obj,
+                prop: MemberProp::Ident(prop),
+                ..
+            }) => obj.as_ident().is_some_and(|obj| &*obj.sym == "Object") && &*prop.sym == "assign",
+            _ => false,
+        },
+        _ => false,
+    };
+    if !is_object_assign || !is_pascal_case(&target.sym) {
+        return vec![];
+    }
+
+    call_expr
+        .args
+        .iter_mut()
+        .skip(1)
+        .filter_map(|arg| match &mut *arg.expr {
+            Expr::Object(object) if arg.spread.is_none() => Some(object),
+            _ => None,
+        })
+        .flat_map(|object| object.props.iter_mut())
+        .filter_map(|prop| {
+            let PropOrSpread::Prop(prop) = prop else {
+                return None;
+            };
+            let (key, is_component) = match &mut **prop {
+                Prop::Shorthand(ident) => (ident.sym.clone(), true),
+                Prop::KeyValue(KeyValueProp {
+                    key: PropName::Ident(key),
+                    value,
+                }) => {
+                    let is_component = match &mut **value {
+                        Expr::Ident(ident) => is_pascal_case(&ident.sym),
+                        value => HasJSXVisitor::test(value, config, imports),
+                    };
+                    (key.sym.clone(), is_component)
+                }
+                _ => return None,
+            };
+
+            (is_component && is_pascal_case(&key)).then(|| Component::member(target, vec![key]))
+        })
+        .collect()
+}
+
+/// Component maps: `const Icons = { Add: () => <svg /> }` names `Icons.Add`.
+/// Nested objects are walked while `depth` allows it.
+fn process_object_literal(
+    root: &Ident,
+    path: &[Atom],
+    object: &mut ObjectLit,
+    depth: usize,
+    config: &Config,
+    imports: &ImportMap,
+) -> Vec<Component> {
+    object
+        .props
+        .iter_mut()
+        .filter_map(|prop| match prop {
+            PropOrSpread::Prop(prop) => match &mut **prop {
+                Prop::KeyValue(KeyValueProp {
+                    key: PropName::Ident(key),
+                    value,
+                }) => Some((key.sym.clone(), value)),
+                _ => None,
+            },
+            PropOrSpread::Spread(_) => None,
+        })
+        .flat_map(|(key, value)| {
+            let mut path = path.to_vec();
+            path.push(key);
+
+            match &mut **value {
+                Expr::Object(object) if depth > 1 => {
+                    process_object_literal(root, &path, object, depth - 1, config, imports)
+                }
+                Expr::Object(_) => vec![],
+                value => HasJSXVisitor::test(value, config, imports)
+                    .then(|| Component::member(root, path))
+                    .into_iter()
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+fn is_pascal_case(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_uppercase())
+}
+
+/// Collects the bindings that are assigned a `displayName`.
+#[derive(Default)]
+struct DisplayNameCollector {
+    /// The root binding and the property path: `(Card, ["Header"])` for `Card.Header`.
+    components_with_display_name: HashSet<(Id, Vec<Atom>)>,
+}
+
+impl Visit for DisplayNameCollector {
+    fn visit_assign_expr(&mut self, expr: &AssignExpr) {
+        expr.visit_children_with(self);
+
+        if let Some(component) = process_assignment_expr(expr) {
+            self.components_with_display_name.insert(component);
+        }
+    }
+}
+
+fn process_assignment_expr(expr: &AssignExpr) -> Option<(Id, Vec<Atom>)> {
     if expr.op != AssignOp::Assign {
         return None;
     }
@@ -254,9 +1287,10 @@ fn process_assignment_expr(expr: &mut AssignExpr) -> Option<Atom> {
                 return None;
             }
 
-            let obj = obj.as_ident()?;
+            let (root, path) = member_path(obj)?;
 
-            Some(obj.sym.clone())
+            // `member_path` includes the root itself
+            Some((root.to_id(), path[1..].to_vec()))
         }
         _ => None,
     }
@@ -264,24 +1298,28 @@ fn process_assignment_expr(expr: &mut AssignExpr) -> Option<Atom> {
 
 fn extract_var_decl_from_stmt(stmt: &mut Stmt) -> Option<&mut VarDecl> {
     match stmt {
-        Stmt::Decl(Decl::Var(var_decl)) => Some(var_decl),
+        Stmt::Decl(Decl::Var(var_decl)) if !var_decl.declare => Some(var_decl),
         _ => None,
     }
 }
 
 fn extract_fn_decl_from_stmt(stmt: &mut Stmt) -> Option<&mut FnDecl> {
     match stmt {
-        Stmt::Decl(Decl::Fn(fn_decl)) => Some(fn_decl),
+        Stmt::Decl(Decl::Fn(fn_decl)) if !fn_decl.declare => Some(fn_decl),
         _ => None,
     }
 }
 
-fn extract_assignment_expr_from_stmt(stmt: &mut Stmt) -> Option<&mut AssignExpr> {
+fn extract_class_decl_from_stmt(stmt: &mut Stmt) -> Option<&mut ClassDecl> {
     match stmt {
-        Stmt::Expr(ExprStmt { expr, .. }) => match &mut **expr {
-            Expr::Assign(assign_expr) => Some(assign_expr),
-            _ => None,
-        },
+        Stmt::Decl(Decl::Class(class_decl)) if !class_decl.declare => Some(class_decl),
+        _ => None,
+    }
+}
+
+fn extract_expr_from_stmt(stmt: &mut Stmt) -> Option<&mut Expr> {
+    match stmt {
+        Stmt::Expr(ExprStmt { expr, .. }) => Some(expr),
         _ => None,
     }
 }
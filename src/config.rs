@@ -0,0 +1,342 @@
+use super::glob;
+use serde::Deserialize;
+use swc_core::common::{BytePos, Span, DUMMY_SP};
+use swc_core::ecma::ast::{EsVersion, Expr, ExprStmt, ModuleItem, Stmt};
+use swc_core::ecma::atoms::Atom;
+use swc_core::ecma::parser::{lexer::Lexer, Parser, StringInput, Syntax};
+use swc_core::ecma::visit::{VisitMut, VisitMutWith};
+
+/// Plugin options, passed as the second element of the SWC plugin tuple:
+/// `["swc-plugin-add-display-name", { ... }]`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub struct Config {
+    /// Glob patterns of files to transform. All files are transformed when empty.
+    pub include: Vec<String>,
+
+    /// Glob patterns of files to leave untouched, even if they are included.
+    pub exclude: Vec<String>,
+
+    /// Additional modules whose component APIs (`memo`, `forwardRef`, `lazy`,
+    /// `createContext`, `observer`, `connect`, `styled`) produce components,
+    /// e.g. a local re-export of `react-redux`.
+    pub import_sources: Vec<String>,
+
+    /// Functions whose calls produce components, like `createIcon` or
+    /// `Animated.createAnimatedComponent`, optionally restricted to an import source.
+    pub component_factories: Vec<ComponentFactory>,
+
+    /// Additional type names that mark a binding as a component, e.g.
+    /// `PolymorphicComponent` in `const Box: PolymorphicComponent = ...`.
+    /// `FC`, `FunctionComponent`, `ComponentType`, ... are always recognized.
+    pub component_types: Vec<String>,
+
+    /// Additional JSX factory names, e.g. `h` for Preact. Compiled JSX calls
+    /// (`jsx`, `jsxs`, `jsxDEV`, `createElement`) are always recognized.
+    pub jsx_factories: Vec<String>,
+
+    /// Root identifiers of styled component tags, e.g. `styled` in
+    /// `styled(Button).attrs({})` or `tw` in `tw.div`, optionally restricted to
+    /// an import source. Defaults to `["styled"]`.
+    pub styled_tags: Vec<StyledTag>,
+
+    /// Runtime modules announced by `/** @jsxImportSource preact */` pragmas.
+    /// Filled in per file, not an option.
+    #[serde(skip)]
+    pub jsx_import_sources: Vec<String>,
+
+    /// Template for components loaded with `lazy`, `next/dynamic` or
+    /// `@loadable/component`, e.g. `"{name} ({kind} {path})"` gives
+    /// `Settings (lazy ./pages/Settings)`. Defaults to the variable name.
+    pub lazy_display_name: Option<String>,
+
+    /// When the plugin should run, depending on the SWC environment.
+    pub mode: Mode,
+
+    /// Which blocks are searched for components.
+    pub scope: Scope,
+
+    /// How the display name is attached to the component.
+    pub emit: Emit,
+
+    /// Name class components with a `static displayName = "X";` class property
+    /// instead of an assignment.
+    pub static_display_name: bool,
+
+    /// Name components wrapped in higher-order components after the wrapping
+    /// chain, e.g. `Connect(Profile)` or `withStyles(Button)`, instead of the
+    /// variable name.
+    pub hoc_display_name: bool,
+
+    /// Name component-like properties of object literals, e.g. `Icons.Add` in
+    /// `const Icons = { Add: () => <svg /> }`, up to this many levels of
+    /// nesting. Disabled when `0`.
+    pub object_literal_depth: usize,
+
+    /// Wraps generated assignments in `if (<condition>) { ... }` so that
+    /// minifiers can drop them from production bundles.
+    pub dev_guard: Option<DevGuard>,
+}
+
+/// Either `"createIcon"` or `{ "name": "createIcon", "source": "@chakra-ui/icons" }`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum ComponentFactory {
+    Name(String),
+    Import { name: String, source: String },
+}
+
+impl ComponentFactory {
+    /// Tells whether a callee like `Animated.createAnimatedComponent` matches,
+    /// given the import source of its first segment.
+    pub fn matches(&self, path: &[Atom], src: Option<&str>) -> bool {
+        let (name, source) = match self {
+            ComponentFactory::Name(name) => (name, None),
+            ComponentFactory::Import { name, source } => (name, Some(source)),
+        };
+
+        source.is_none_or(|source| src == Some(source.as_str()))
+            && name.split('.').eq(path.iter().map(|segment| &**segment))
+    }
+}
+
+/// Either `"tw"` or `{ "name": "tw", "source": "tailwind-styled-components" }`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum StyledTag {
+    Name(String),
+    Import { name: String, source: String },
+}
+
+impl StyledTag {
+    /// Tells whether a tag root named `root`, imported from `src`, matches.
+    pub fn matches(&self, root: &str, src: Option<&str>) -> bool {
+        match self {
+            StyledTag::Name(name) => name == root,
+            StyledTag::Import { name, source } => name == root && src == Some(source.as_str()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Mode {
+    /// Run regardless of the environment.
+    #[default]
+    Always,
+
+    /// Run unless the environment is `production`.
+    DevelopmentOnly,
+
+    /// Never run. Handy for toggling the plugin from a shared config.
+    Never,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Scope {
+    /// Only the top level of the module.
+    #[default]
+    TopLevel,
+
+    /// Every block: function bodies, `if` blocks, IIFEs, test callbacks...
+    All,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Emit {
+    /// `X.displayName = "X";` after the declaration.
+    #[default]
+    Assignment,
+
+    /// `const X = /*#__PURE__*/ Object.assign(init, { displayName: "X" });`
+    /// so that unused components stay tree-shakable. Declarations that can't be
+    /// wrapped, like function declarations, still get an assignment.
+    Pure,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub struct DevGuard {
+    /// Defaults to `process.env.NODE_ENV !== "production"`.
+    pub condition: GuardCondition,
+
+    /// Emit one guarded block at the end of the module instead of guarding
+    /// each assignment separately. Nested blocks are always guarded per assignment.
+    pub batch: bool,
+}
+
+/// A JavaScript expression parsed from the options, e.g. `__DEV__` or
+/// `import.meta.env.DEV`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(try_from = "String")]
+pub struct GuardCondition(pub Box<Expr>);
+
+impl Default for GuardCondition {
+    fn default() -> Self {
+        GuardCondition(
+            parse_expr(r#"process.env.NODE_ENV !== "production""#)
+                .expect("the default guard condition is valid"),
+        )
+    }
+}
+
+impl TryFrom<String> for GuardCondition {
+    type Error = String;
+
+    fn try_from(src: String) -> Result<Self, Self::Error> {
+        parse_expr(&src).map(GuardCondition)
+    }
+}
+
+fn parse_expr(src: &str) -> Result<Box<Expr>, String> {
+    let invalid = || format!("`{src}` is not a valid JavaScript expression");
+
+    let lexer = Lexer::new(
+        Syntax::Es(Default::default()),
+        EsVersion::EsNext,
+        StringInput::new(src, BytePos(0), BytePos(src.len() as u32)),
+        None,
+    );
+    let mut parser = Parser::new_from(lexer);
+    let module = parser.parse_module().map_err(|_| invalid())?;
+    if !parser.take_errors().is_empty() {
+        return Err(invalid());
+    }
+
+    match <[ModuleItem; 1]>::try_from(module.body) {
+        Ok([ModuleItem::Stmt(Stmt::Expr(ExprStmt { mut expr, .. }))]) => {
+            // The positions point into the options string, not into the compiled file
+            expr.visit_mut_with(&mut DropSpans);
+            Ok(expr)
+        }
+        _ => Err(invalid()),
+    }
+}
+
+struct DropSpans;
+
+impl VisitMut for DropSpans {
+    fn visit_mut_span(&mut self, span: &mut Span) {
+        *span = DUMMY_SP;
+    }
+}
+
+/// Modules whose component APIs are recognized without configuration.
+const COMPONENT_SOURCES: &[&str] = &[
+    "react",
+    "react-redux",
+    "mobx-react",
+    "mobx-react-lite",
+    "styled-components",
+    "@emotion/styled",
+];
+
+/// Types of component bindings recognized without configuration.
+const COMPONENT_TYPES: &[&str] = &[
+    "FC",
+    "FunctionComponent",
+    "VFC",
+    "VoidFunctionComponent",
+    "ComponentType",
+    "ForwardRefExoticComponent",
+    "MemoExoticComponent",
+];
+
+/// Names of compiled JSX calls recognized without configuration.
+const JSX_FACTORIES: &[&str] = &[
+    "jsx",
+    "jsxs",
+    "_jsx",
+    "_jsxs",
+    "jsxDEV",
+    "_jsxDEV",
+    "createElement",
+];
+
+impl Config {
+    /// Parses the raw JSON options handed over by SWC.
+    ///
+    /// Missing or empty options fall back to the defaults. Unknown keys and
+    /// values of the wrong type are rejected so that typos don't go unnoticed.
+    pub fn parse(raw: Option<&str>) -> Result<Config, String> {
+        match raw.map(str::trim) {
+            None | Some("") | Some("null") => Ok(Config::default()),
+            Some(json) => serde_json::from_str(json).map_err(|err| err.to_string()),
+        }
+    }
+
+    /// Tells whether `mode` allows the plugin to run in the given SWC environment
+    /// (SWC's `envName`, which defaults to `NODE_ENV`).
+    pub fn is_enabled(&self, env: Option<&str>) -> bool {
+        match self.mode {
+            Mode::Always => true,
+            Mode::DevelopmentOnly => env != Some("production"),
+            Mode::Never => false,
+        }
+    }
+
+    /// Tells whether the file being compiled is selected by `include`/`exclude`.
+    ///
+    /// Files without a name (e.g. code passed via stdin) are always transformed.
+    pub fn should_transform(&self, filename: Option<&str>) -> bool {
+        let Some(filename) = filename else {
+            return true;
+        };
+
+        let included = self.include.is_empty()
+            || self
+                .include
+                .iter()
+                .any(|pattern| glob::is_match(pattern, filename));
+
+        included
+            && !self
+                .exclude
+                .iter()
+                .any(|pattern| glob::is_match(pattern, filename))
+    }
+
+    /// Tells whether component APIs imported from `src` should be trusted.
+    pub fn is_component_source(&self, src: &str) -> bool {
+        COMPONENT_SOURCES.contains(&src) || self.import_sources.iter().any(|s| s == src)
+    }
+
+    /// Tells whether a binding typed `name` (e.g. `React.FC`) is a component.
+    pub fn is_component_type(&self, name: &str) -> bool {
+        let last = name.rsplit('.').next().unwrap_or(name);
+
+        COMPONENT_TYPES.contains(&last)
+            || self
+                .component_types
+                .iter()
+                .any(|ty| ty == name || ty == last)
+    }
+
+    /// Tells whether templates tagged with `name.x`, `name(X)`, ... are styled
+    /// components, given the import source of `name`.
+    ///
+    /// Without `styledTags`, only `styled` imported from a component source
+    /// like `styled-components` counts.
+    pub fn is_styled_tag(&self, name: &str, src: Option<&str>) -> bool {
+        match &self.styled_tags[..] {
+            [] => name == "styled" && src.is_some_and(|src| self.is_component_source(src)),
+            tags => tags.iter().any(|tag| tag.matches(name, src)),
+        }
+    }
+
+    /// Tells whether calls to `name` create elements, like `jsx(...)` or `h(...)`.
+    pub fn is_jsx_factory(&self, name: &str) -> bool {
+        JSX_FACTORIES.contains(&name) || self.jsx_factories.iter().any(|n| n == name)
+    }
+
+    /// Tells whether `src` is the runtime of a `@jsxImportSource`, e.g.
+    /// `preact/jsx-runtime` for `@jsxImportSource preact`.
+    pub fn is_jsx_runtime(&self, src: &str) -> bool {
+        self.jsx_import_sources.iter().any(|source| {
+            src.strip_prefix(source.as_str())
+                .is_some_and(|rest| matches!(rest, "" | "/jsx-runtime" | "/jsx-dev-runtime"))
+        })
+    }
+}
@@ -0,0 +1,122 @@
+/// Matches a file path against a glob pattern.
+///
+/// Supported syntax:
+/// - `*` matches any sequence of characters except `/`
+/// - `**` matches any sequence of characters including `/`
+/// - `?` matches a single character except `/`
+/// - `{a,b}` matches either alternative
+///
+/// Backslashes in the path are treated as `/`. Patterns that don't start with
+/// `/` are relative and may match at any directory boundary, so `src/**`
+/// matches both `src/index.tsx` and `/home/me/project/src/index.tsx`. A
+/// leading `./` is ignored, so `./src/**` is the same as `src/**`.
+pub fn is_match(pattern: &str, path: &str) -> bool {
+    let path = path.replace('\\', "/");
+
+    expand_braces(pattern).iter().any(|pattern| {
+        let pattern = pattern.strip_prefix("./").unwrap_or(pattern);
+        let pattern = pattern.as_bytes();
+        let path = path.as_bytes();
+
+        if matches(pattern, path) {
+            return true;
+        }
+
+        if pattern.first() == Some(&b'/') {
+            return false;
+        }
+
+        path.iter()
+            .enumerate()
+            .filter(|(_, c)| **c == b'/')
+            .any(|(i, _)| matches(pattern, &path[i + 1..]))
+    })
+}
+
+fn matches(pattern: &[u8], path: &[u8]) -> bool {
+    match pattern {
+        [] => path.is_empty(),
+        [b'*', b'*', rest @ ..] => {
+            // `**/` may also match zero directories
+            if let [b'/', after @ ..] = rest {
+                if matches(after, path) {
+                    return true;
+                }
+            }
+            (0..=path.len()).any(|i| matches(rest, &path[i..]))
+        }
+        [b'*', rest @ ..] => (0..=path.len())
+            .take_while(|&i| i == 0 || path[i - 1] != b'/')
+            .any(|i| matches(rest, &path[i..])),
+        [b'?', rest @ ..] => match path {
+            [c, path @ ..] if *c != b'/' => matches(rest, path),
+            _ => false,
+        },
+        [c, rest @ ..] => match path {
+            [p, path @ ..] if p == c => matches(rest, path),
+            _ => false,
+        },
+    }
+}
+
+fn expand_braces(pattern: &str) -> Vec<String> {
+    let Some(start) = pattern.find('{') else {
+        return vec![pattern.to_string()];
+    };
+    let Some(len) = pattern[start..].find('}') else {
+        return vec![pattern.to_string()];
+    };
+
+    let (prefix, alternatives, suffix) = (
+        &pattern[..start],
+        &pattern[start + 1..start + len],
+        &pattern[start + len + 1..],
+    );
+
+    alternatives
+        .split(',')
+        .flat_map(|alt| expand_braces(&format!("{prefix}{alt}{suffix}")))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::is_match;
+
+    #[test]
+    fn star_does_not_cross_directories() {
+        assert!(is_match("src/*.tsx", "src/App.tsx"));
+        assert!(!is_match("src/*.tsx", "src/components/App.tsx"));
+    }
+
+    #[test]
+    fn double_star_crosses_directories() {
+        assert!(is_match("src/**/*.tsx", "src/App.tsx"));
+        assert!(is_match("src/**/*.tsx", "src/components/App.tsx"));
+        assert!(is_match(
+            "**/node_modules/**",
+            "/app/node_modules/react/index.js"
+        ));
+    }
+
+    #[test]
+    fn relative_patterns_match_at_directory_boundaries() {
+        assert!(is_match("src/**", "/home/me/project/src/index.tsx"));
+        assert!(!is_match("src/**", "/home/me/project/mysrc/index.tsx"));
+        assert!(!is_match("/src/**", "/home/me/project/src/index.tsx"));
+    }
+
+    #[test]
+    fn leading_dot_slash_is_ignored() {
+        assert!(is_match("./src/**", "/home/me/project/src/index.tsx"));
+        assert!(is_match("./src/**", "src/index.tsx"));
+        assert!(!is_match("./src/**", "/home/me/project/mysrc/index.tsx"));
+    }
+
+    #[test]
+    fn braces_and_backslashes() {
+        assert!(is_match("**/*.{test,spec}.tsx", "src/App.spec.tsx"));
+        assert!(is_match("src/**/*.tsx", "C:\\project\\src\\App.tsx"));
+        assert!(!is_match("**/*.{test,spec}.tsx", "src/App.tsx"));
+    }
+}
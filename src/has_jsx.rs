@@ -1,65 +1,161 @@
+use super::add_display_name::unwrap_expr;
+use super::config::Config;
+use super::imports::{str_value, Import, ImportMap};
+use std::collections::HashSet;
 use swc_core::ecma::{
     ast::*,
-    visit::{VisitMut, VisitMutWith},
+    atoms::Atom,
+    visit::{Visit, VisitMut, VisitMutWith, VisitWith},
 };
 
-pub struct HasJSXVisitor {
+pub struct HasJSXVisitor<'a> {
+    config: &'a Config,
+    imports: &'a ImportMap,
     inside_fn: usize,
-    has_jsx: bool,
+    in_value: bool,
+    returns_element: bool,
     has_component_api_calls: bool,
 }
 
-impl HasJSXVisitor {
-    pub fn test(node: &mut impl VisitMutWith<Self>) -> bool {
-        let mut visitor = HasJSXVisitor {
+/// A component loaded on demand, e.g. `lazy(() => import("./Settings"))`.
+pub struct LazyComponent {
+    /// `lazy`, `dynamic` (`next/dynamic`) or `loadable` (`@loadable/component`).
+    pub kind: &'static str,
+
+    /// The dynamic import specifier, e.g. `./Settings`.
+    pub path: Option<Atom>,
+}
+
+impl<'a> HasJSXVisitor<'a> {
+    fn new(config: &'a Config, imports: &'a ImportMap) -> Self {
+        HasJSXVisitor {
+            config,
+            imports,
             inside_fn: 0,
-            has_jsx: false,
+            in_value: true,
+            returns_element: false,
             has_component_api_calls: false,
-        };
+        }
+    }
+
+    pub fn test(
+        node: &mut impl VisitMutWith<Self>,
+        config: &'a Config,
+        imports: &'a ImportMap,
+    ) -> bool {
+        let mut visitor = HasJSXVisitor::new(config, imports);
         node.visit_mut_with(&mut visitor);
-        visitor.has_jsx || visitor.has_component_api_calls
+        visitor.returns_element || visitor.has_component_api_calls
+    }
+
+    /// `const Empty: React.FC = () => null` is a component even without JSX.
+    pub fn is_component_type_ann(type_ann: &TsTypeAnn, config: &Config) -> bool {
+        type_ref_name(&type_ann.type_ann).is_some_and(|name| config.is_component_type(&name))
+    }
+
+    pub fn lazy_component(
+        expr: &Expr,
+        config: &'a Config,
+        imports: &'a ImportMap,
+    ) -> Option<LazyComponent> {
+        let Expr::Call(call_expr) = expr else {
+            return None;
+        };
+        let kind = HasJSXVisitor::new(config, imports).lazy_kind(call_expr)?;
+
+        let mut finder = DynamicImportFinder::default();
+        call_expr.args.visit_with(&mut finder);
+
+        Some(LazyComponent {
+            kind,
+            path: finder.path,
+        })
     }
 }
 
-impl VisitMut for HasJSXVisitor {
+impl VisitMut for HasJSXVisitor<'_> {
     fn visit_mut_fn_decl(&mut self, el: &mut FnDecl) {
+        let returns_element = returns_element_type(el.function.return_type.as_deref())
+            || el
+                .function
+                .body
+                .as_ref()
+                .is_some_and(|body| self.block_returns_element(body));
+        self.mark_function(returns_element);
+
         self.inside_fn += 1;
         el.visit_mut_children_with(self);
         self.inside_fn -= 1;
     }
 
     fn visit_mut_fn_expr(&mut self, el: &mut FnExpr) {
+        let returns_element = returns_element_type(el.function.return_type.as_deref())
+            || el
+                .function
+                .body
+                .as_ref()
+                .is_some_and(|body| self.block_returns_element(body));
+        self.mark_function(returns_element);
+
         self.inside_fn += 1;
         el.visit_mut_children_with(self);
         self.inside_fn -= 1;
     }
 
     fn visit_mut_arrow_expr(&mut self, el: &mut ArrowExpr) {
+        let returns_element = returns_element_type(el.return_type.as_deref())
+            || match &*el.body {
+                BlockStmtOrExpr::BlockStmt(body) => self.block_returns_element(body),
+                BlockStmtOrExpr::Expr(expr) => self.is_element(expr),
+            };
+        self.mark_function(returns_element);
+
         self.inside_fn += 1;
         el.visit_mut_children_with(self);
         self.inside_fn -= 1;
     }
 
-    fn visit_mut_jsx_element(&mut self, el: &mut JSXElement) {
+    fn visit_mut_class(&mut self, el: &mut Class) {
+        let is_component = self.is_class_component(el);
+        self.mark_function(is_component);
+
+        self.inside_fn += 1;
         el.visit_mut_children_with(self);
-        self.mark_jsx();
+        self.inside_fn -= 1;
     }
 
-    fn visit_mut_jsx_fragment(&mut self, el: &mut JSXFragment) {
-        el.visit_mut_children_with(self);
-        self.mark_jsx();
+    fn visit_mut_expr(&mut self, expr: &mut Expr) {
+        // Functions only count when they are the value itself, possibly wrapped
+        // in `memo(...)` and the like, not in `list.map(x => <li />)`
+        let in_value = self.in_value;
+        self.in_value = in_value
+            && matches!(
+                expr,
+                Expr::Fn(_)
+                    | Expr::Arrow(_)
+                    | Expr::Class(_)
+                    | Expr::Call(_)
+                    | Expr::Paren(_)
+                    | Expr::TsAs(_)
+                    | Expr::TsSatisfies(_)
+                    | Expr::TsNonNull(_)
+                    | Expr::TsTypeAssertion(_)
+                    | Expr::TsConstAssertion(_)
+            );
+        expr.visit_mut_children_with(self);
+        self.in_value = in_value;
     }
 
     fn visit_mut_call_expr(&mut self, call_expr: &mut CallExpr) {
-        call_expr.visit_mut_children_with(self);
-
-        // Check if this is a React JSX runtime call or createElement call
-        if self.is_react_call(call_expr) {
-            self.mark_jsx();
-        }
+        let in_value = self.in_value;
+        self.in_value = false;
+        call_expr.callee.visit_mut_with(self);
+        self.in_value = in_value && self.is_wrapper_call(call_expr);
+        call_expr.args.visit_mut_with(self);
+        self.in_value = in_value;
 
         // Check for component API calls
-        if self.is_component_api_call(call_expr) {
+        if self.is_component_api_call(call_expr) || self.lazy_kind(call_expr).is_some() {
             self.has_component_api_calls = true;
         }
     }
@@ -73,56 +169,257 @@ impl VisitMut for HasJSXVisitor {
     }
 }
 
-impl HasJSXVisitor {
-    fn mark_jsx(&mut self) {
-        if self.inside_fn > 0 {
-            self.has_jsx = true;
+impl HasJSXVisitor<'_> {
+    /// Only the outermost functions and classes count:
+    /// `() => { const render = () => <div />; return render; }` returns a function, not an element.
+    fn mark_function(&mut self, returns_element: bool) {
+        if self.inside_fn == 0 && self.in_value && returns_element {
+            self.returns_element = true;
+        }
+    }
+
+    /// `memo(...)`, `forwardRef(...)`, `connect(mapState)(...)`, `withRouter(...)`,
+    /// `someHoc(...)`...
+    ///
+    /// Unlike component API calls, the callee doesn't need to be imported: the
+    /// wrapped function still has to return an element. Methods like
+    /// `list.map(...)` and hooks like `useMemo(...)` don't make components.
+    fn is_wrapper_call(&self, call_expr: &CallExpr) -> bool {
+        let Callee::Expr(callee) = &call_expr.callee else {
+            return false;
+        };
+
+        self.is_component_api_call(call_expr)
+            || match &**callee {
+                Expr::Call(call_expr) => self.is_wrapper_call(call_expr),
+                Expr::Ident(ident) => !is_hook_name(&ident.sym),
+                callee => member_path(callee)
+                    .and_then(|(_, path)| path.last().cloned())
+                    .is_some_and(|name| is_component_api(&name) || is_hoc_name(&name)),
+            }
+    }
+
+    fn block_returns_element(&self, body: &BlockStmt) -> bool {
+        let mut visitor = ReturnsElementVisitor {
+            parent: self,
+            elements: HashSet::new(),
+            returns_element: false,
+        };
+        body.visit_with(&mut visitor);
+        visitor.returns_element
+    }
+
+    /// A class component either extends `Component`/`PureComponent` or has a
+    /// `render` method returning an element.
+    fn is_class_component(&self, class: &Class) -> bool {
+        let extends_component = class
+            .super_class
+            .as_deref()
+            .is_some_and(|super_class| self.is_component_base_class(super_class));
+
+        extends_component
+            || class.body.iter().any(|member| match member {
+                ClassMember::Method(ClassMethod {
+                    key: PropName::Ident(key),
+                    function,
+                    kind: MethodKind::Method,
+                    is_static: false,
+                    ..
+                }) if &*key.sym == "render" => function
+                    .body
+                    .as_ref()
+                    .is_some_and(|body| self.block_returns_element(body)),
+                _ => false,
+            })
+    }
+
+    fn is_component_base_class(&self, expr: &Expr) -> bool {
+        match expr {
+            // class Foo extends Component {}
+            Expr::Ident(ident) => match self.imports.get(ident) {
+                Some(import) if self.config.is_component_source(&import.src) => {
+                    matches!(
+                        import.imported.as_deref(),
+                        Some("Component" | "PureComponent")
+                    )
+                }
+                _ => false,
+            },
+            // class Foo extends React.Component {}
+            Expr::Member(MemberExpr {
+                obj,
+                prop: MemberProp::Ident(prop),
+                ..
+            }) => {
+                matches!(&*prop.sym, "Component" | "PureComponent")
+                    && self
+                        .namespace_import(obj)
+                        .is_some_and(|import| self.config.is_component_source(&import.src))
+            }
+            _ => false,
+        }
+    }
+
+    /// Tells whether an expression evaluates to a React element, at least in
+    /// some branches: `cond ? <div /> : null`, `show && <div />`, ...
+    fn is_element(&self, expr: &Expr) -> bool {
+        self.is_element_with(expr, &HashSet::new())
+    }
+
+    /// Same as `is_element`, also accepting the local variables in `elements`.
+    fn is_element_with(&self, expr: &Expr, elements: &HashSet<Id>) -> bool {
+        // `(<div />) as any`, `<div /> as unknown as JSX.Element`, ...
+        match unwrap_expr(expr) {
+            Expr::JSXElement(_) | Expr::JSXFragment(_) => true,
+            Expr::Ident(ident) => elements.contains(&ident.to_id()),
+            Expr::Call(call_expr) => {
+                self.is_react_call(call_expr)
+                    || is_element_call(call_expr)
+                    || self.is_element_map(call_expr)
+            }
+            Expr::Cond(CondExpr { cons, alt, .. }) => {
+                self.is_element_with(cons, elements) || self.is_element_with(alt, elements)
+            }
+            Expr::Bin(BinExpr {
+                op: BinaryOp::LogicalAnd | BinaryOp::LogicalOr | BinaryOp::NullishCoalescing,
+                left,
+                right,
+                ..
+            }) => self.is_element_with(left, elements) || self.is_element_with(right, elements),
+            Expr::Seq(SeqExpr { exprs, .. }) => exprs
+                .last()
+                .is_some_and(|expr| self.is_element_with(expr, elements)),
+            _ => false,
         }
     }
 
+    /// `items.map(item => <li />)` renders a list of elements.
+    fn is_element_map(&self, call_expr: &CallExpr) -> bool {
+        let Callee::Expr(callee) = &call_expr.callee else {
+            return false;
+        };
+        let Expr::Member(MemberExpr {
+            prop: MemberProp::Ident(prop),
+            ..
+        }) = &**callee
+        else {
+            return false;
+        };
+        if !matches!(&*prop.sym, "map" | "flatMap") {
+            return false;
+        }
+
+        call_expr.args.first().is_some_and(|arg| match &*arg.expr {
+            Expr::Arrow(arrow_expr) => match &*arrow_expr.body {
+                BlockStmtOrExpr::BlockStmt(body) => self.block_returns_element(body),
+                BlockStmtOrExpr::Expr(expr) => self.is_element(expr),
+            },
+            Expr::Fn(fn_expr) => fn_expr
+                .function
+                .body
+                .as_ref()
+                .is_some_and(|body| self.block_returns_element(body)),
+            _ => false,
+        })
+    }
+
     fn is_react_call(&self, call_expr: &CallExpr) -> bool {
         match &call_expr.callee {
-            Callee::Expr(expr) => match &**expr {
-                // JSX Runtime functions: jsx("div", { children: "..." })
-                // Legacy createElement: createElement("div", { ... })
-                Expr::Ident(ident) => {
-                    matches!(ident.sym.as_ref(), "jsx" | "jsxs" | "_jsx" | "_jsxs" | "jsxDEV" | "_jsxDEV" | "createElement")
+            Callee::Expr(expr) => self.is_jsx_callee(expr),
+            Callee::Super(_) | Callee::Import(_) => false,
+        }
+    }
+
+    fn is_jsx_callee(&self, expr: &Expr) -> bool {
+        match expr {
+            // JSX Runtime functions: jsx("div", { children: "..." })
+            // Legacy createElement: createElement("div", { ... })
+            // Custom pragmas: h("div", { ... })
+            Expr::Ident(ident) => {
+                self.config.is_jsx_factory(&ident.sym) || self.is_jsx_runtime_import(ident)
+            }
+
+            // Namespace calls: React.createElement("div", { ... })
+            // Only accept single-level member expressions (X.method) where X is not 'document',
+            // plus the CommonJS interop form `_react.default.createElement`.
+            // Reject other nested member expressions (a.b.method)
+            Expr::Member(member_expr) => {
+                let MemberProp::Ident(prop_ident) = &member_expr.prop else {
+                    return false;
+                };
+                if !self.config.is_jsx_factory(&prop_ident.sym) {
+                    return false;
                 }
 
-                // Namespace calls: React.createElement("div", { ... })
-                // Only accept single-level member expressions (X.method) where X is not 'document'
-                // Reject nested member expressions (a.b.method)
-                Expr::Member(member_expr) => {
-                    if let MemberProp::Ident(prop_ident) = &member_expr.prop {
-                        // Check if this is a React method
-                        if matches!(prop_ident.sym.as_ref(), "jsx" | "jsxs" | "_jsx" | "_jsxs" | "jsxDEV" | "_jsxDEV" | "createElement") {
-                            // Only accept if the object is a single identifier (not nested)
-                            // and it's not 'document'
-                            if let Expr::Ident(obj_ident) = &*member_expr.obj {
-                                return obj_ident.sym.as_ref() != "document";
-                            }
-                        }
-                    }
-                    false
+                match &*member_expr.obj {
+                    Expr::Ident(obj_ident) => obj_ident.sym.as_ref() != "document",
+                    Expr::Member(MemberExpr {
+                        obj,
+                        prop: MemberProp::Ident(default),
+                        ..
+                    }) => &*default.sym == "default" && obj.is_ident(),
+                    _ => false,
                 }
+            }
 
-                _ => false,
-            },
-            Callee::Super(_) | Callee::Import(_) => false,
+            // CommonJS output calls the runtime without a `this`: (0, _jsxRuntime.jsx)("div", {})
+            Expr::Paren(ParenExpr { expr, .. }) => self.is_jsx_callee(expr),
+            Expr::Seq(SeqExpr { exprs, .. }) => {
+                exprs.last().is_some_and(|expr| self.is_jsx_callee(expr))
+            }
+
+            _ => false,
+        }
+    }
+
+    /// The import behind the object of `React.memo`, or of `_react.default.memo`
+    /// in CommonJS output.
+    fn namespace_import(&self, obj: &Expr) -> Option<&Import> {
+        match obj {
+            Expr::Ident(ident) => self.imports.get(ident),
+            Expr::Member(MemberExpr {
+                obj,
+                prop: MemberProp::Ident(default),
+                ..
+            }) if &*default.sym == "default" => self.imports.get(obj.as_ident()?),
+            _ => None,
+        }
+    }
+
+    /// `import { jsx as j } from "preact/jsx-runtime"` with `@jsxImportSource preact`.
+    fn is_jsx_runtime_import(&self, ident: &Ident) -> bool {
+        match self.imports.get(ident) {
+            Some(import) if self.config.is_jsx_runtime(&import.src) => matches!(
+                import.imported.as_deref(),
+                Some("jsx" | "jsxs" | "jsxDEV" | "h" | "createElement")
+            ),
+            _ => false,
         }
     }
 
     fn is_component_api_call(&self, call_expr: &CallExpr) -> bool {
+        if let Callee::Expr(callee) = &call_expr.callee {
+            if self.is_component_factory(callee) {
+                return true;
+            }
+        }
+
         match &call_expr.callee {
             Callee::Expr(expr) => match &**expr {
                 // Direct calls: createContext(...)
-                Expr::Ident(ident) => {
-                    matches!(ident.sym.as_ref(), "createContext" | "observer" | "connect" | "styled")
-                }
+                Expr::Ident(ident) => match self.imports.get(ident) {
+                    Some(import) if self.config.is_component_source(&import.src) => {
+                        is_component_api(self.imports.imported_name(ident))
+                    }
+                    _ => false,
+                },
                 // Member calls: MobX.observer(...)
                 Expr::Member(member_expr) => {
                     if let MemberProp::Ident(prop_ident) = &member_expr.prop {
-                        matches!(prop_ident.sym.as_ref(), "createContext" | "observer" | "connect" | "styled")
+                        is_component_api(&prop_ident.sym)
+                            && self
+                                .namespace_import(&member_expr.obj)
+                                .is_some_and(|import| self.config.is_component_source(&import.src))
                     } else {
                         false
                     }
@@ -133,22 +430,221 @@ impl HasJSXVisitor {
         }
     }
 
-    fn is_styled_component_template(&self, tagged_tpl: &TaggedTpl) -> bool {
-        match &*tagged_tpl.tag {
-            // Handle any styled.something`` pattern
-            Expr::Member(member_expr) => {
-                if let MemberProp::Ident(_) = &member_expr.prop {
-                    if let Expr::Ident(obj_ident) = &*member_expr.obj {
-                        // Check if it's styled.something``
-                        obj_ident.sym.as_ref() == "styled"
-                    } else {
-                        false
-                    }
-                } else {
-                    false
+    /// Matches the `componentFactories` option. The first segment of the callee
+    /// is resolved through imports, so `import { createIcon as icon }` still matches.
+    fn is_component_factory(&self, callee: &Expr) -> bool {
+        if self.config.component_factories.is_empty() {
+            return false;
+        }
+        let Some((root, mut path)) = member_path(callee) else {
+            return false;
+        };
+
+        path[0] = self.imports.imported_name(root).clone();
+        let import = self.imports.get(root);
+
+        self.config
+            .component_factories
+            .iter()
+            .any(|factory| factory.matches(&path, import.map(|import| &*import.src)))
+    }
+
+    fn lazy_kind(&self, call_expr: &CallExpr) -> Option<&'static str> {
+        let Callee::Expr(callee) = &call_expr.callee else {
+            return None;
+        };
+
+        match &**callee {
+            // lazy(...), dynamic(...), loadable(...)
+            Expr::Ident(ident) => {
+                let import = self.imports.get(ident)?;
+                match (&*import.src, import.imported.as_deref()) {
+                    ("next/dynamic", None) => Some("dynamic"),
+                    ("@loadable/component", None) => Some("loadable"),
+                    (src, Some("lazy")) if self.config.is_component_source(src) => Some("lazy"),
+                    _ => None,
                 }
             }
-            _ => false,
+            // React.lazy(...)
+            Expr::Member(MemberExpr {
+                obj,
+                prop: MemberProp::Ident(prop),
+                ..
+            }) if &*prop.sym == "lazy" => {
+                let import = self.namespace_import(obj)?;
+                self.config
+                    .is_component_source(&import.src)
+                    .then_some("lazy")
+            }
+            _ => None,
+        }
+    }
+
+    /// `styled.div`, `styled(Button)`, `styled("div")`, `styled.div.attrs({})`,
+    /// `styled.button.withConfig({})`...
+    fn is_styled_component_template(&self, tagged_tpl: &TaggedTpl) -> bool {
+        // A bare `styled` tag isn't a styled component
+        if tagged_tpl.tag.is_ident() {
+            return false;
+        }
+
+        let Some(root) = styled_root(&tagged_tpl.tag) else {
+            return false;
+        };
+
+        let import = self.imports.get(root);
+        self.config.is_styled_tag(
+            self.imports.imported_name(root),
+            import.map(|import| &*import.src),
+        )
+    }
+}
+
+/// Hooks named like `useMemo`.
+fn is_hook_name(name: &str) -> bool {
+    name.strip_prefix("use")
+        .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_uppercase()))
+}
+
+/// `createPortal(<div />, node)` and `cloneElement(children, props)` return
+/// elements as well.
+fn is_element_call(call_expr: &CallExpr) -> bool {
+    let Callee::Expr(callee) = &call_expr.callee else {
+        return false;
+    };
+
+    member_path(callee)
+        .and_then(|(_, path)| path.last().cloned())
+        .is_some_and(|name| matches!(&*name, "createPortal" | "cloneElement"))
+}
+
+/// Higher-order components named like `withRouter`.
+pub fn is_hoc_name(name: &str) -> bool {
+    name.strip_prefix("with")
+        .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_uppercase()))
+}
+
+fn is_component_api(name: &str) -> bool {
+    matches!(
+        name,
+        "createContext" | "observer" | "connect" | "styled" | "memo" | "forwardRef" | "lazy"
+    )
+}
+
+/// Walks a tag through member and call chains: `styled.div.attrs({})` gives `styled`.
+fn styled_root(expr: &Expr) -> Option<&Ident> {
+    match expr {
+        Expr::Ident(ident) => Some(ident),
+        Expr::Member(MemberExpr { obj, .. }) => styled_root(obj),
+        Expr::Call(CallExpr {
+            callee: Callee::Expr(callee),
+            ..
+        }) => styled_root(callee),
+        _ => None,
+    }
+}
+
+/// Return types of components: `JSX.Element`, `React.ReactNode`, `ReactElement | null`.
+fn returns_element_type(return_type: Option<&TsTypeAnn>) -> bool {
+    fn is_element_type(ty: &TsType) -> bool {
+        match ty {
+            TsType::TsUnionOrIntersectionType(TsUnionOrIntersectionType::TsUnionType(union)) => {
+                union.types.iter().any(|ty| is_element_type(ty))
+            }
+            ty => type_ref_name(ty).is_some_and(|name| {
+                name == "JSX.Element"
+                    || name.ends_with(".JSX.Element")
+                    || matches!(name.rsplit('.').next(), Some("ReactNode" | "ReactElement"))
+            }),
+        }
+    }
+
+    return_type.is_some_and(|return_type| is_element_type(&return_type.type_ann))
+}
+
+/// `React.FC<Props>` gives `React.FC`.
+fn type_ref_name(ty: &TsType) -> Option<String> {
+    fn entity_name(name: &TsEntityName) -> String {
+        match name {
+            TsEntityName::Ident(ident) => ident.sym.to_string(),
+            TsEntityName::TsQualifiedName(qualified) => {
+                format!("{}.{}", entity_name(&qualified.left), qualified.right.sym)
+            }
+        }
+    }
+
+    match ty {
+        TsType::TsTypeRef(TsTypeRef { type_name, .. }) => Some(entity_name(type_name)),
+        _ => None,
+    }
+}
+
+/// Splits `Animated.createAnimatedComponent` into its root identifier and the
+/// names along the path.
+pub fn member_path(expr: &Expr) -> Option<(&Ident, Vec<Atom>)> {
+    match expr {
+        Expr::Ident(ident) => Some((ident, vec![ident.sym.clone()])),
+        Expr::Member(MemberExpr {
+            obj,
+            prop: MemberProp::Ident(prop),
+            ..
+        }) => {
+            let (root, mut path) = member_path(obj)?;
+            path.push(prop.sym.clone());
+            Some((root, path))
+        }
+        _ => None,
+    }
+}
+
+/// Looks for `return <element>` in a function body, without entering nested functions.
+struct ReturnsElementVisitor<'a, 'b> {
+    parent: &'b HasJSXVisitor<'a>,
+    /// Local variables holding elements: `const el = <div />; return el;`
+    elements: HashSet<Id>,
+    returns_element: bool,
+}
+
+impl Visit for ReturnsElementVisitor<'_, '_> {
+    fn visit_return_stmt(&mut self, stmt: &ReturnStmt) {
+        if let Some(arg) = &stmt.arg {
+            if self.parent.is_element_with(arg, &self.elements) {
+                self.returns_element = true;
+            }
+        }
+    }
+
+    fn visit_var_declarator(&mut self, declarator: &VarDeclarator) {
+        declarator.visit_children_with(self);
+
+        if let (Pat::Ident(binding), Some(init)) = (&declarator.name, &declarator.init) {
+            if self.parent.is_element_with(init, &self.elements) {
+                self.elements.insert(binding.to_id());
+            }
+        }
+    }
+
+    fn visit_function(&mut self, _: &Function) {}
+
+    fn visit_arrow_expr(&mut self, _: &ArrowExpr) {}
+
+    fn visit_class(&mut self, _: &Class) {}
+}
+
+/// Finds the first `import("...")` specifier.
+#[derive(Default)]
+struct DynamicImportFinder {
+    path: Option<Atom>,
+}
+
+impl Visit for DynamicImportFinder {
+    fn visit_call_expr(&mut self, call_expr: &CallExpr) {
+        call_expr.visit_children_with(self);
+
+        if let (Callee::Import(_), Some(arg)) = (&call_expr.callee, call_expr.args.first()) {
+            if let Expr::Lit(Lit::Str(str)) = &*arg.expr {
+                self.path = self.path.take().or_else(|| str_value(str));
+            }
         }
     }
 }
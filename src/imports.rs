@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+use swc_core::ecma::{ast::*, atoms::Atom};
+
+pub struct Import {
+    /// The module specifier, e.g. `react-redux`.
+    pub src: Atom,
+
+    /// The exported name for named imports, `None` for default and namespace imports.
+    pub imported: Option<Atom>,
+}
+
+/// Maps the top-level bindings introduced by `import` declarations and
+/// `require` calls to their source.
+#[derive(Default)]
+pub struct ImportMap {
+    imports: HashMap<Id, Import>,
+}
+
+impl ImportMap {
+    pub fn collect(items: &[ModuleItem]) -> Self {
+        let mut map = ImportMap::default();
+
+        items.iter().for_each(|item| match item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl)) if !import_decl.type_only => {
+                map.add_import(import_decl)
+            }
+            ModuleItem::Stmt(stmt) => map.add_require(stmt),
+            _ => {}
+        });
+
+        map
+    }
+
+    pub fn collect_script(stmts: &[Stmt]) -> Self {
+        let mut map = ImportMap::default();
+        stmts.iter().for_each(|stmt| map.add_require(stmt));
+        map
+    }
+
+    fn add_import(&mut self, import_decl: &ImportDecl) {
+        let Some(src) = str_value(&import_decl.src) else {
+            return;
+        };
+
+        import_decl.specifiers.iter().for_each(|specifier| {
+            let (local, imported) = match specifier {
+                ImportSpecifier::Named(named) if !named.is_type_only => {
+                    let imported = match &named.imported {
+                        Some(ModuleExportName::Ident(ident)) => Some(ident.sym.clone()),
+                        Some(ModuleExportName::Str(str)) => str_value(str),
+                        None => Some(named.local.sym.clone()),
+                    };
+                    (&named.local, imported)
+                }
+                ImportSpecifier::Default(default) => (&default.local, None),
+                ImportSpecifier::Namespace(namespace) => (&namespace.local, None),
+                _ => return,
+            };
+
+            self.insert(local, &src, imported);
+        })
+    }
+
+    /// `const React = require("react")`, `const { memo } = require("react")` and
+    /// the `_interopRequireDefault(require("react"))` of CommonJS output.
+    fn add_require(&mut self, stmt: &Stmt) {
+        let Stmt::Decl(Decl::Var(var_decl)) = stmt else {
+            return;
+        };
+
+        for declarator in &var_decl.decls {
+            let Some(src) = declarator.init.as_deref().and_then(require_source) else {
+                continue;
+            };
+
+            match &declarator.name {
+                Pat::Ident(binding) => self.insert(&binding.id, &src, None),
+                Pat::Object(ObjectPat { props, .. }) => {
+                    props.iter().for_each(|prop| match prop {
+                        // const { memo } = require("react")
+                        ObjectPatProp::Assign(AssignPatProp {
+                            key, value: None, ..
+                        }) => self.insert(&key.id, &src, Some(key.sym.clone())),
+                        // const { memo: m } = require("react")
+                        ObjectPatProp::KeyValue(KeyValuePatProp { key, value }) => {
+                            let imported = match key {
+                                PropName::Ident(ident) => Some(ident.sym.clone()),
+                                PropName::Str(str) => str_value(str),
+                                _ => None,
+                            };
+                            if let (Some(imported), Pat::Ident(local)) = (imported, &**value) {
+                                self.insert(&local.id, &src, Some(imported));
+                            }
+                        }
+                        _ => {}
+                    })
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn insert(&mut self, local: &Ident, src: &Atom, imported: Option<Atom>) {
+        self.imports.insert(
+            local.to_id(),
+            Import {
+                src: src.clone(),
+                imported,
+            },
+        );
+    }
+
+    pub fn get(&self, ident: &Ident) -> Option<&Import> {
+        self.imports.get(&ident.to_id())
+    }
+
+    /// The name `ident` is exported under, so that aliases like
+    /// `import { memo as m }` are matched by their exported name. Default,
+    /// namespace and non-imported bindings keep their local name.
+    pub fn imported_name<'a>(&'a self, ident: &'a Ident) -> &'a Atom {
+        self.get(ident)
+            .and_then(|import| import.imported.as_ref())
+            .unwrap_or(&ident.sym)
+    }
+}
+
+pub fn str_value(str: &Str) -> Option<Atom> {
+    str.value.as_str().map(Atom::from)
+}
+
+/// The module of `require("react")`, also behind the `_interopRequireDefault`
+/// and `_interopRequireWildcard` helpers.
+fn require_source(expr: &Expr) -> Option<Atom> {
+    let Expr::Call(CallExpr {
+        callee: Callee::Expr(callee),
+        args,
+        ..
+    }) = expr
+    else {
+        return None;
+    };
+
+    match (&**callee, args.as_slice()) {
+        (Expr::Ident(ident), [ExprOrSpread { spread: None, expr }]) => match (&*ident.sym, &**expr)
+        {
+            ("require", Expr::Lit(Lit::Str(str))) => str_value(str),
+            (name, expr) if name.starts_with("_interopRequire") => require_source(expr),
+            _ => None,
+        },
+        _ => None,
+    }
+}
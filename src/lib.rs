@@ -1,15 +1,37 @@
 mod add_display_name;
+mod config;
+mod glob;
 mod has_jsx;
+mod imports;
 
 use add_display_name::AddDisplayNameVisitor;
+use config::Config;
 use swc_core::ecma::ast::Program;
 use swc_core::ecma::visit::visit_mut_pass;
+use swc_core::plugin::metadata::TransformPluginMetadataContextKind;
 use swc_core::plugin::plugin_transform;
 use swc_core::plugin::proxies::TransformPluginProgramMetadata;
 
 #[plugin_transform]
-pub fn process_transform(program: Program, _metadata: TransformPluginProgramMetadata) -> Program {
-    program.apply(&mut visit_mut_pass(AddDisplayNameVisitor::default()))
+pub fn process_transform(program: Program, metadata: TransformPluginProgramMetadata) -> Program {
+    let config = Config::parse(metadata.get_transform_plugin_config().as_deref())
+        .unwrap_or_else(|err| panic!("swc-plugin-add-display-name: invalid plugin options: {err}"));
+
+    let env = metadata.get_context(&TransformPluginMetadataContextKind::Env);
+    if !config.is_enabled(env.as_deref()) {
+        return program;
+    }
+
+    let filename = metadata.get_context(&TransformPluginMetadataContextKind::Filename);
+    if !config.should_transform(filename.as_deref()) {
+        return program;
+    }
+
+    program.apply(&mut visit_mut_pass(AddDisplayNameVisitor::new(
+        config,
+        filename,
+        metadata.comments,
+    )))
 }
 
 #[cfg(test)]
@@ -32,13 +54,88 @@ mod test {
         disallow_ambiguous_jsx_like: true,
     });
 
-    fn runner(_: &mut Tester) -> impl Pass {
+    /// Runs the plugin and prints the result as is, with its comments and
+    /// without the fixer of `test_inline!`.
+    fn transform(src: &str, options: &str) -> String {
+        Tester::run(|t| {
+            let pass = runner_with(t, options);
+            let program = t.apply_transform(pass, "input.tsx", SYNTAX, None, src)?;
+            Ok(t.print(&program, &t.comments.clone()))
+        })
+    }
+
+    #[test]
+    fn config_defaults_when_empty() {
+        assert!(super::Config::parse(None).is_ok());
+        assert!(super::Config::parse(Some("{}")).is_ok());
+    }
+
+    #[test]
+    fn config_rejects_unknown_keys() {
+        let err = super::Config::parse(Some(r#"{ "unknownOption": true }"#)).unwrap_err();
+        assert!(err.contains("unknownOption"));
+    }
+
+    #[test]
+    fn config_include_exclude() {
+        let config = super::Config::parse(Some(
+            r#"{ "include": ["src/**"], "exclude": ["**/*.test.tsx"] }"#,
+        ))
+        .unwrap();
+
+        assert!(config.should_transform(Some("/app/src/Button.tsx")));
+        assert!(!config.should_transform(Some("/app/src/Button.test.tsx")));
+        assert!(!config.should_transform(Some("/app/node_modules/lib/index.js")));
+        assert!(config.should_transform(None));
+    }
+
+    #[test]
+    fn config_mode() {
+        let parse = |json: &str| super::Config::parse(Some(json)).unwrap();
+
+        assert!(parse("{}").is_enabled(Some("production")));
+        assert!(parse(r#"{ "mode": "development-only" }"#).is_enabled(Some("development")));
+        assert!(parse(r#"{ "mode": "development-only" }"#).is_enabled(None));
+        assert!(!parse(r#"{ "mode": "development-only" }"#).is_enabled(Some("production")));
+        assert!(!parse(r#"{ "mode": "never" }"#).is_enabled(Some("development")));
+        assert!(super::Config::parse(Some(r#"{ "mode": "sometimes" }"#)).is_err());
+    }
+
+    fn runner(t: &mut Tester) -> impl Pass {
+        runner_with(t, "{}")
+    }
+
+    fn runner_with(t: &mut Tester, options: &str) -> impl Pass {
+        runner_for_file(t, None, options)
+    }
+
+    fn runner_for_file(t: &mut Tester, filename: Option<&str>, options: &str) -> impl Pass {
+        let config = super::Config::parse(Some(options)).unwrap();
         (
             resolver(Mark::new(), Mark::new(), false),
-            visit_mut_pass(super::AddDisplayNameVisitor::default()),
+            visit_mut_pass(super::AddDisplayNameVisitor::new(
+                config,
+                filename.map(String::from),
+                Some(t.comments.clone()),
+            )),
         )
     }
 
+    #[test]
+    fn config_rejects_invalid_guard_condition() {
+        let err =
+            super::Config::parse(Some(r#"{ "devGuard": { "condition": "if (" } }"#)).unwrap_err();
+        assert!(err.contains("not a valid JavaScript expression"));
+    }
+
+    #[test]
+    fn config_rejects_invalid_component_factory() {
+        assert!(
+            super::Config::parse(Some(r#"{ "componentFactories": [{ "name": "x" }] }"#)).is_err()
+        );
+        assert!(super::Config::parse(Some(r#"{ "componentFactories": [42] }"#)).is_err());
+    }
+
     test_inline!(
         SYNTAX,
         runner,
@@ -350,7 +447,208 @@ mod test {
                 const render = () => <div />;
                 return render;
             };
-            fn.displayName = "fn";
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ should_not_work_on_fns_passing_jsx_to_callbacks,
+        /* Input */
+        r#"
+            const mount = () => {
+                root.render(<App />);
+            };
+            function renderTo(container) {
+                const element = <App />;
+                ReactDOM.render(element, container);
+                return container;
+            }
+        "#,
+        /* Output */
+        r#"
+            const mount = () => {
+                root.render(<App />);
+            };
+            function renderTo(container) {
+                const element = <App />;
+                ReactDOM.render(element, container);
+                return container;
+            }
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ returning_element_or_null,
+        /* Input */
+        r#"
+            export function Maybe({ show }) {
+                if (!show) {
+                    return null;
+                }
+                return <div />;
+            }
+            export const Logical = ({ show }) => show && <div />;
+            export const Fallback = ({ children }) => children ?? <span />;
+        "#,
+        /* Output */
+        r#"
+            export function Maybe({ show }) {
+                if (!show) {
+                    return null;
+                }
+                return <div />;
+            }
+            Maybe.displayName = "Maybe";
+            export const Logical = ({ show }) => show && <div />;
+            Logical.displayName = "Logical";
+            export const Fallback = ({ children }) => children ?? <span />;
+            Fallback.displayName = "Fallback";
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ returning_element_from_nested_block,
+        /* Input */
+        r#"
+            export function List({ items }) {
+                for (const item of items) {
+                    if (item.selected) {
+                        return <Item item={item} />;
+                    }
+                }
+                return null;
+            }
+        "#,
+        /* Output */
+        r#"
+            export function List({ items }) {
+                for (const item of items) {
+                    if (item.selected) {
+                        return <Item item={item} />;
+                    }
+                }
+                return null;
+            }
+            List.displayName = "List";
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ should_not_work_on_callbacks_in_initializers,
+        /* Input */
+        r#"
+            const items = list.map(x => <li />);
+            const rows = useMemo(() => <tr />, []);
+        "#,
+        /* Output */
+        r#"
+            const items = list.map(x => <li />);
+            const rows = useMemo(() => <tr />, []);
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ returning_local_element,
+        /* Input */
+        r#"
+            function App() {
+                const el = <div />;
+                return el;
+            }
+        "#,
+        /* Output */
+        r#"
+            function App() {
+                const el = <div />;
+                return el;
+            }
+            App.displayName = "App";
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ returning_mapped_elements,
+        /* Input */
+        r#"
+            const List = ({ items }) => items.map(i => <li />);
+            function Rows({ rows }) {
+                return rows.map(function (row) { return <tr />; });
+            }
+        "#,
+        /* Output */
+        r#"
+            const List = ({ items }) => items.map(i => <li />);
+            List.displayName = "List";
+            function Rows({ rows }) {
+                return rows.map(function (row) { return <tr />; });
+            }
+            Rows.displayName = "Rows";
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ returning_asserted_elements,
+        /* Input */
+        r#"
+            export const Icon = () => <svg /> as unknown as JSX.Element;
+            export function Box() {
+                return (<div />) as any;
+            }
+        "#,
+        /* Output */
+        r#"
+            export const Icon = () => <svg /> as unknown as JSX.Element;
+            Icon.displayName = "Icon";
+            export function Box() {
+                return <div /> as any;
+            }
+            Box.displayName = "Box";
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ returning_portals_and_cloned_elements,
+        /* Input */
+        r#"
+            export const Modal = ({ node }) => createPortal(<div />, node);
+            export const Slot = ({ children }) => React.cloneElement(children, { slot: true });
+        "#,
+        /* Output */
+        r#"
+            export const Modal = ({ node }) => createPortal(<div />, node);
+            Modal.displayName = "Modal";
+            export const Slot = ({ children }) => React.cloneElement(children, { slot: true });
+            Slot.displayName = "Slot";
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ unknown_hoc_wrappers,
+        /* Input */
+        r#"
+            export const Themed = someUnknownHoc(() => <div />);
+        "#,
+        /* Output */
+        r#"
+            export const Themed = someUnknownHoc(() => <div />);
+            Themed.displayName = "Themed";
         "#
     );
 
@@ -433,6 +731,70 @@ mod test {
         "#
     );
 
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ should_not_duplicate_guarded_existed,
+        /* Input */
+        r#"
+            export const Component = () => <div />;
+            if (process.env.NODE_ENV !== "production") {
+                Component.displayName = "Component";
+            }
+        "#,
+        /* Output */
+        r#"
+            export const Component = () => <div />;
+            if (process.env.NODE_ENV !== "production") {
+                Component.displayName = "Component";
+            }
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ shadowed_existed_should_not_count,
+        /* Input */
+        r#"
+            export const Component = () => <div />;
+            function setup() {
+                const Component = {};
+                Component.displayName = "Other";
+            }
+        "#,
+        /* Output */
+        r#"
+            export const Component = () => <div />;
+            Component.displayName = "Component";
+            function setup() {
+                const Component = {};
+                Component.displayName = "Other";
+            }
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ shadowed_param_existed_should_not_count,
+        /* Input */
+        r#"
+            export function Component() { return <div />; }
+            export function patch(Component) {
+                Component.displayName = "Patched";
+            }
+        "#,
+        /* Output */
+        r#"
+            export function Component() { return <div />; }
+            Component.displayName = "Component";
+            export function patch(Component) {
+                Component.displayName = "Patched";
+            }
+        "#
+    );
+
     // Tests for JSX Runtime functions and createElement
 
     test_inline!(
@@ -808,11 +1170,11 @@ mod test {
         runner,
         /* Name */ mobx_observer_member_call,
         /* Input */ r#"
-            import MobX from 'mobx';
+            import * as MobX from 'mobx-react-lite';
             export const ObservedComponent = MobX.observer(() => console.log('no jsx'));
         "#,
         /* Output */ r#"
-            import MobX from 'mobx';
+            import * as MobX from 'mobx-react-lite';
             export const ObservedComponent = MobX.observer(() => console.log('no jsx'));
             ObservedComponent.displayName = "ObservedComponent";
         "#
@@ -911,4 +1273,1603 @@ mod test {
             Component.displayName = "Component";
         "#
     );
+
+    // Development guard
+
+    test_inline!(
+        SYNTAX,
+        |t| runner_with(t, r#"{ "devGuard": {} }"#),
+        /* Name */ dev_guard_each,
+        /* Input */
+        r#"
+            export const Foo = () => <div />;
+            export const Bar = () => <div />;
+        "#,
+        /* Output */
+        r#"
+            export const Foo = () => <div />;
+            if (process.env.NODE_ENV !== "production") {
+                Foo.displayName = "Foo";
+            }
+            export const Bar = () => <div />;
+            if (process.env.NODE_ENV !== "production") {
+                Bar.displayName = "Bar";
+            }
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        |t| runner_with(
+            t,
+            r#"{ "devGuard": { "condition": "__DEV__", "batch": true } }"#
+        ),
+        /* Name */ dev_guard_batch,
+        /* Input */
+        r#"
+            export const Foo = () => <div />;
+            export const Bar = () => <div />;
+            export default Foo;
+        "#,
+        /* Output */
+        r#"
+            export const Foo = () => <div />;
+            export const Bar = () => <div />;
+            export default Foo;
+            if (__DEV__) {
+                Foo.displayName = "Foo";
+                Bar.displayName = "Bar";
+            }
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        |t| runner_with(
+            t,
+            r#"{ "devGuard": { "condition": "import.meta.env.DEV" } }"#
+        ),
+        /* Name */ dev_guard_import_meta,
+        /* Input */ r#"
+            export const Foo = () => <div />;
+        "#,
+        /* Output */
+        r#"
+            export const Foo = () => <div />;
+            if (import.meta.env.DEV) {
+                Foo.displayName = "Foo";
+            }
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        |t| runner_with(t, r#"{ "devGuard": { "batch": true } }"#),
+        /* Name */ dev_guard_batch_without_components,
+        /* Input */ r#"
+            export const fn = () => console.log();
+        "#,
+        /* Output */ r#"
+            export const fn = () => console.log();
+        "#
+    );
+
+    // Tree-shakable output
+
+    test_inline!(
+        SYNTAX,
+        |t| runner_with(t, r#"{ "emit": "pure" }"#),
+        /* Name */ pure_var_declarations,
+        /* Input */
+        r#"
+            export const Foo = () => <div />;
+            export const Bar = memo(() => <div />);
+        "#,
+        /* Output */
+        r#"
+            export const Foo = /*#__PURE__*/ Object.assign(() => <div />, { displayName: "Foo" });
+            export const Bar = /*#__PURE__*/ Object.assign(memo(() => <div />), { displayName: "Bar" });
+        "#
+    );
+
+    #[test]
+    fn pure_annotation_is_emitted() {
+        // `test_inline!` doesn't compare comments
+        let output = transform("export const Foo = () => <div />;", r#"{ "emit": "pure" }"#);
+        assert!(
+            output.contains("= /*#__PURE__*/ Object.assign("),
+            "{output}"
+        );
+    }
+
+    #[test]
+    fn pure_annotation_keeps_existing_annotations() {
+        let output = transform(
+            "export const Foo = /*#__PURE__*/ memo(() => <div />);",
+            r#"{ "emit": "pure" }"#,
+        );
+        assert!(
+            output.contains("= /*#__PURE__*/ Object.assign(/*#__PURE__*/ memo("),
+            "{output}"
+        );
+    }
+
+    test_inline!(
+        SYNTAX,
+        |t| runner_with(t, r#"{ "emit": "pure" }"#),
+        /* Name */ pure_class_components,
+        /* Input */
+        r#"
+            import { Component } from 'react';
+            export class Foo extends Component {}
+        "#,
+        /* Output */
+        r#"
+            import { Component } from 'react';
+            export class Foo extends Component {
+                static displayName = "Foo";
+            }
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        |t| runner_with(t, r#"{ "emit": "pure" }"#),
+        /* Name */ pure_falls_back_for_fn_declarations,
+        /* Input */ r#"
+            export function Foo() { return <div />; }
+        "#,
+        /* Output */
+        r#"
+            export function Foo() { return <div />; }
+            Foo.displayName = "Foo";
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        |t| runner_with(t, r#"{ "emit": "pure" }"#),
+        /* Name */ pure_respects_existing,
+        /* Input */
+        r#"
+            export const Foo = () => <div />;
+            Foo.displayName = "Custom";
+        "#,
+        /* Output */
+        r#"
+            export const Foo = () => <div />;
+            Foo.displayName = "Custom";
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        |t| runner_with(
+            t,
+            r#"{ "emit": "pure", "devGuard": { "condition": "__DEV__" } }"#
+        ),
+        /* Name */ pure_with_dev_guard,
+        /* Input */ r#"
+            export const Foo = () => <div />;
+        "#,
+        /* Output */
+        r#"
+            export const Foo = /*#__PURE__*/ Object.assign(() => <div />, __DEV__ ? { displayName: "Foo" } : {});
+        "#
+    );
+
+    // Import sources of component APIs
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ aliased_component_api_import,
+        /* Input */
+        r#"
+            import { connect as reduxConnect } from 'react-redux';
+            export const ConnectedComponent = reduxConnect(null)(() => null);
+        "#,
+        /* Output */
+        r#"
+            import { connect as reduxConnect } from 'react-redux';
+            export const ConnectedComponent = reduxConnect(null)(() => null);
+            ConnectedComponent.displayName = "ConnectedComponent";
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ should_not_work_on_unrelated_library_api,
+        /* Input */
+        r#"
+            import { connect } from 'my-database-client';
+            export const db = connect(url);
+        "#,
+        /* Output */
+        r#"
+            import { connect } from 'my-database-client';
+            export const db = connect(url);
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ should_not_work_on_local_api_lookalike,
+        /* Input */
+        r#"
+            function observer(fn) { return fn; }
+            export const store = observer(() => console.log('no jsx'));
+        "#,
+        /* Output */
+        r#"
+            function observer(fn) { return fn; }
+            export const store = observer(() => console.log('no jsx'));
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ should_not_work_on_unresolved_api_call,
+        /* Input */ r#"
+            export const db = connect(url);
+        "#,
+        /* Output */ r#"
+            export const db = connect(url);
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        |t| runner_with(t, r#"{ "importSources": ["~/lib/redux"] }"#),
+        /* Name */ configured_import_source,
+        /* Input */
+        r#"
+            import { connect } from '~/lib/redux';
+            export const ConnectedComponent = connect(null)(() => null);
+        "#,
+        /* Output */
+        r#"
+            import { connect } from '~/lib/redux';
+            export const ConnectedComponent = connect(null)(() => null);
+            ConnectedComponent.displayName = "ConnectedComponent";
+        "#
+    );
+
+    // Class components
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ class_extends_react_component,
+        /* Input */
+        r#"
+            import React from 'react';
+            export class Foo extends React.Component {
+                render() { return this.props.children; }
+            }
+        "#,
+        /* Output */
+        r#"
+            import React from 'react';
+            export class Foo extends React.Component {
+                render() { return this.props.children; }
+            }
+            Foo.displayName = "Foo";
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ class_default_export_pure_component,
+        /* Input */
+        r#"
+            import { PureComponent } from 'react';
+            export default class Foo extends PureComponent {}
+        "#,
+        /* Output */
+        r#"
+            import { PureComponent } from 'react';
+            export default class Foo extends PureComponent {}
+            Foo.displayName = "Foo";
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ class_render_returning_jsx,
+        /* Input */
+        r#"
+            class Foo extends Base {
+                renderItem = () => <li />;
+                render() { return <div />; }
+            }
+            const Bar = class extends Base {
+                render() { return this.props.show ? <div /> : null; }
+            };
+        "#,
+        /* Output */
+        r#"
+            class Foo extends Base {
+                renderItem = () => <li />;
+                render() { return <div />; }
+            }
+            Foo.displayName = "Foo";
+            const Bar = class extends Base {
+                render() { return this.props.show ? <div /> : null; }
+            };
+            Bar.displayName = "Bar";
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ should_not_work_on_non_component_class,
+        /* Input */
+        r#"
+            class Store extends Base {
+                renderItem = () => <li />;
+                load() { return fetch(url); }
+            }
+            class HttpError extends Error {}
+        "#,
+        /* Output */
+        r#"
+            class Store extends Base {
+                renderItem = () => <li />;
+                load() { return fetch(url); }
+            }
+            class HttpError extends Error {}
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ class_should_respect_static_display_name,
+        /* Input */
+        r#"
+            import { Component } from 'react';
+            export class Foo extends Component {
+                static displayName = "Custom";
+            }
+        "#,
+        /* Output */
+        r#"
+            import { Component } from 'react';
+            export class Foo extends Component {
+                static displayName = "Custom";
+            }
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        |t| runner_with(t, r#"{ "staticDisplayName": true }"#),
+        /* Name */ class_static_display_name,
+        /* Input */
+        r#"
+            import { Component } from 'react';
+            export class Foo extends Component {
+                render() { return <div />; }
+            }
+            export const Bar = class extends Component {};
+            export const Baz = () => <div />;
+        "#,
+        /* Output */
+        r#"
+            import { Component } from 'react';
+            export class Foo extends Component {
+                static displayName = "Foo";
+                render() { return <div />; }
+            }
+            export const Bar = class extends Component {
+                static displayName = "Bar";
+            };
+            export const Baz = () => <div />;
+            Baz.displayName = "Baz";
+        "#
+    );
+
+    // Anonymous default exports
+
+    test_inline!(
+        SYNTAX,
+        |t| runner_for_file(t, Some("/app/src/components/UserCard.tsx"), "{}"),
+        /* Name */ default_export_anonymous_fn,
+        /* Input */ r#"
+            export default function () { return <div />; }
+        "#,
+        /* Output */
+        r#"
+            export default function UserCard() { return <div />; }
+            UserCard.displayName = "UserCard";
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        |t| runner_for_file(t, Some("/app/src/user-card/index.tsx"), "{}"),
+        /* Name */ default_export_arrow_named_after_directory,
+        /* Input */ r#"
+            export default (() => <div />);
+        "#,
+        /* Output */
+        r#"
+            const UserCard = () => <div />;
+            UserCard.displayName = "UserCard";
+            export default UserCard;
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        |t| runner_for_file(t, Some("C:\\app\\src\\404.page.tsx"), "{}"),
+        /* Name */ default_export_anonymous_class,
+        /* Input */
+        r#"
+            import { Component } from 'react';
+            export default class extends Component {}
+        "#,
+        /* Output */
+        r#"
+            import { Component } from 'react';
+            export default class _404 extends Component {}
+            _404.displayName = "_404";
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        |t| runner_for_file(t, Some("/app/src/utils.ts"), "{}"),
+        /* Name */ should_not_name_non_component_default_export,
+        /* Input */ r#"
+            export default () => console.log();
+        "#,
+        /* Output */ r#"
+            export default (() => console.log());
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ default_export_wrapped_named_fn,
+        /* Input */
+        r#"
+            export default memo(function Card() { return <div />; });
+        "#,
+        /* Output */
+        r#"
+            const Card = memo(function Card() { return <div />; });
+            Card.displayName = "Card";
+            export default Card;
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        |t| runner_for_file(t, Some("/app/src/Dashboard.tsx"), "{}"),
+        /* Name */ default_export_wrapped_anonymous_fn,
+        /* Input */
+        r#"
+            import { observer } from 'mobx-react';
+            export default observer(() => <div />);
+        "#,
+        /* Output */
+        r#"
+            import { observer } from 'mobx-react';
+            const Dashboard = observer(() => <div />);
+            Dashboard.displayName = "Dashboard";
+            export default Dashboard;
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        |t| runner_for_file(t, Some("/app/src/ProfileContainer.tsx"), "{}"),
+        /* Name */ default_export_connected_component,
+        /* Input */
+        r#"
+            import { connect } from 'react-redux';
+            import { Profile } from './Profile';
+            export default connect(mapState)(Profile);
+        "#,
+        /* Output */
+        r#"
+            import { connect } from 'react-redux';
+            import { Profile } from './Profile';
+            const ProfileContainer = connect(mapState)(Profile);
+            ProfileContainer.displayName = "ProfileContainer";
+            export default ProfileContainer;
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        |t| runner_for_file(t, Some("/app/src/config.ts"), "{}"),
+        /* Name */ should_not_hoist_non_component_default_export,
+        /* Input */ r#"
+            export default defineConfig({ plugins: [] });
+        "#,
+        /* Output */ r#"
+            export default defineConfig({ plugins: [] });
+        "#
+    );
+
+    // Wrappers without inline JSX
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ memo_forward_ref_lazy_without_jsx,
+        /* Input */
+        r#"
+            import React, { memo, lazy } from 'react';
+            import { BaseButton } from './BaseButton';
+            export const Fancy = memo(BaseButton);
+            export const Ref = React.forwardRef(renderFn);
+            export const Settings = lazy(loadSettings);
+        "#,
+        /* Output */
+        r#"
+            import React, { memo, lazy } from 'react';
+            import { BaseButton } from './BaseButton';
+            export const Fancy = memo(BaseButton);
+            Fancy.displayName = "Fancy";
+            export const Ref = React.forwardRef(renderFn);
+            Ref.displayName = "Ref";
+            export const Settings = lazy(loadSettings);
+            Settings.displayName = "Settings";
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ should_not_work_on_unresolved_memo_without_jsx,
+        /* Input */
+        r#"
+            import { memo } from 'lodash-es';
+            export const cached = memo(compute);
+            export const Fancy = forwardRef(renderFn);
+        "#,
+        /* Output */
+        r#"
+            import { memo } from 'lodash-es';
+            export const cached = memo(compute);
+            export const Fancy = forwardRef(renderFn);
+        "#
+    );
+
+    // Lazy components
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ lazy_dynamic_loadable,
+        /* Input */
+        r#"
+            import { lazy } from 'react';
+            import dynamic from 'next/dynamic';
+            import loadable from '@loadable/component';
+            export const Settings = lazy(() => import("./pages/Settings"));
+            export const Chart = dynamic(() => import("./Chart"), { ssr: false });
+            export const Editor = loadable(() => import("./Editor"));
+        "#,
+        /* Output */
+        r#"
+            import { lazy } from 'react';
+            import dynamic from 'next/dynamic';
+            import loadable from '@loadable/component';
+            export const Settings = lazy(() => import("./pages/Settings"));
+            Settings.displayName = "Settings";
+            export const Chart = dynamic(() => import("./Chart"), { ssr: false });
+            Chart.displayName = "Chart";
+            export const Editor = loadable(() => import("./Editor"));
+            Editor.displayName = "Editor";
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        |t| runner_with(t, r#"{ "lazyDisplayName": "{name} ({kind} {path})" }"#),
+        /* Name */ lazy_display_name_template,
+        /* Input */
+        r#"
+            import React from 'react';
+            import dynamic from 'next/dynamic';
+            export const Settings = React.lazy(() => import("./pages/Settings"));
+            export const Chart = dynamic(() => import("./Chart").then((mod) => mod.Chart));
+            export const Loaded = React.lazy(load);
+            export const Plain = () => <div />;
+        "#,
+        /* Output */
+        r#"
+            import React from 'react';
+            import dynamic from 'next/dynamic';
+            export const Settings = React.lazy(() => import("./pages/Settings"));
+            Settings.displayName = "Settings (lazy ./pages/Settings)";
+            export const Chart = dynamic(() => import("./Chart").then((mod) => mod.Chart));
+            Chart.displayName = "Chart (dynamic ./Chart)";
+            export const Loaded = React.lazy(load);
+            Loaded.displayName = "Loaded (lazy Loaded)";
+            export const Plain = () => <div />;
+            Plain.displayName = "Plain";
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ should_not_work_on_unrelated_dynamic,
+        /* Input */
+        r#"
+            import { dynamic } from './utils';
+            export const value = dynamic(() => import("./data.json"));
+        "#,
+        /* Output */
+        r#"
+            import { dynamic } from './utils';
+            export const value = dynamic(() => import("./data.json"));
+        "#
+    );
+
+    // Higher-order components
+
+    test_inline!(
+        SYNTAX,
+        |t| runner_with(t, r#"{ "hocDisplayName": true }"#),
+        /* Name */ hoc_display_name_local_component,
+        /* Input */
+        r#"
+            import { withStyles } from '@material-ui/core';
+            import { withRouter } from 'react-router-dom';
+            const Button = () => <button />;
+            export const StyledButton = withStyles(styles)(Button);
+            export const RoutedButton = withRouter(withStyles(styles)(Button));
+        "#,
+        /* Output */
+        r#"
+            import { withStyles } from '@material-ui/core';
+            import { withRouter } from 'react-router-dom';
+            const Button = () => <button />;
+            Button.displayName = "Button";
+            export const StyledButton = withStyles(styles)(Button);
+            StyledButton.displayName = "withStyles(Button)";
+            export const RoutedButton = withRouter(withStyles(styles)(Button));
+            RoutedButton.displayName = "withRouter(withStyles(Button))";
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        |t| runner_with(t, r#"{ "hocDisplayName": true }"#),
+        /* Name */ hoc_display_name_imported_component,
+        /* Input */
+        r#"
+            import { connect } from 'react-redux';
+            import { withRouter } from 'react-router-dom';
+            import Profile from './Profile';
+            export const ConnectedProfile = connect(mapState)(Profile);
+            export const RoutedProfile = withRouter(connect(mapState)(Profile));
+        "#,
+        /* Output */
+        r#"
+            import { connect } from 'react-redux';
+            import { withRouter } from 'react-router-dom';
+            import Profile from './Profile';
+            export const ConnectedProfile = connect(mapState)(Profile);
+            ConnectedProfile.displayName = "Connect(" + (Profile.displayName || Profile.name || "Component") + ")";
+            export const RoutedProfile = withRouter(connect(mapState)(Profile));
+            RoutedProfile.displayName = "withRouter(Connect(" + (Profile.displayName || Profile.name || "Component") + "))";
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        |t| runner_with(t, r#"{ "hocDisplayName": true }"#),
+        /* Name */ should_not_work_on_unknown_hocs,
+        /* Input */
+        r#"
+            import { connect } from 'some-db';
+            const Pool = connect(url)(Config);
+            const Themed = withTheme(Config);
+        "#,
+        /* Output */
+        r#"
+            import { connect } from 'some-db';
+            const Pool = connect(url)(Config);
+            const Themed = withTheme(Config);
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ hoc_display_name_disabled_by_default,
+        /* Input */
+        r#"
+            import { connect } from 'react-redux';
+            import Profile from './Profile';
+            export const ConnectedProfile = connect(mapState)(Profile);
+            export const StyledButton = withStyles(styles)(Button);
+        "#,
+        /* Output */
+        r#"
+            import { connect } from 'react-redux';
+            import Profile from './Profile';
+            export const ConnectedProfile = connect(mapState)(Profile);
+            ConnectedProfile.displayName = "ConnectedProfile";
+            export const StyledButton = withStyles(styles)(Button);
+        "#
+    );
+
+    // Component factories
+
+    test_inline!(
+        SYNTAX,
+        |t| runner_with(
+            t,
+            r#"{
+                "componentFactories": [
+                    "createPolymorphicComponent",
+                    { "name": "createIcon", "source": "@chakra-ui/icons" },
+                    { "name": "Animated.createAnimatedComponent", "source": "react-native" }
+                ]
+            }"#
+        ),
+        /* Name */ component_factories,
+        /* Input */
+        r#"
+            import { createIcon as icon } from '@chakra-ui/icons';
+            import { Animated, View } from 'react-native';
+            import { createPolymorphicComponent } from './polymorphic';
+            export const CheckIcon = icon({ path: check });
+            export const AnimatedView = Animated.createAnimatedComponent(View);
+            export const Box = createPolymorphicComponent(BoxBase);
+        "#,
+        /* Output */
+        r#"
+            import { createIcon as icon } from '@chakra-ui/icons';
+            import { Animated, View } from 'react-native';
+            import { createPolymorphicComponent } from './polymorphic';
+            export const CheckIcon = icon({ path: check });
+            CheckIcon.displayName = "CheckIcon";
+            export const AnimatedView = Animated.createAnimatedComponent(View);
+            AnimatedView.displayName = "AnimatedView";
+            export const Box = createPolymorphicComponent(BoxBase);
+            Box.displayName = "Box";
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        |t| runner_with(
+            t,
+            r#"{ "componentFactories": [{ "name": "createIcon", "source": "@chakra-ui/icons" }] }"#
+        ),
+        /* Name */ component_factories_check_import_source,
+        /* Input */
+        r#"
+            import { createIcon } from './icons';
+            export const CheckIcon = createIcon({ path: check });
+            export const icons = createSvgIcon(check);
+        "#,
+        /* Output */
+        r#"
+            import { createIcon } from './icons';
+            export const CheckIcon = createIcon({ path: check });
+            export const icons = createSvgIcon(check);
+        "#
+    );
+
+    // JSX factories
+
+    test_inline!(
+        SYNTAX,
+        |t| runner_with(t, r#"{ "jsxFactories": ["h"] }"#),
+        /* Name */ jsx_factories_option,
+        /* Input */
+        r#"
+            import { h } from 'preact';
+            export const Button = () => h("button", null);
+            export const Card = () => Preact.h("div", null);
+        "#,
+        /* Output */
+        r#"
+            import { h } from 'preact';
+            export const Button = () => h("button", null);
+            Button.displayName = "Button";
+            export const Card = () => Preact.h("div", null);
+            Card.displayName = "Card";
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ jsx_pragma,
+        /* Input */
+        r#"
+            /** @jsx h */
+            import { h } from 'preact';
+            export const Button = () => h("button", null);
+        "#,
+        /* Output */
+        r#"
+            /** @jsx h */
+            import { h } from 'preact';
+            export const Button = () => h("button", null);
+            Button.displayName = "Button";
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ jsx_import_source_pragma,
+        /* Input */
+        r#"
+            /** @jsxImportSource preact */
+            import { jsx as j } from 'preact/jsx-runtime';
+            export const Button = () => j("button", {});
+        "#,
+        /* Output */
+        r#"
+            /** @jsxImportSource preact */
+            import { jsx as j } from 'preact/jsx-runtime';
+            export const Button = () => j("button", {});
+            Button.displayName = "Button";
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ jsx_pragma_in_script,
+        /* Input */
+        r#"
+            /** @jsx h */
+            const { h } = require('preact');
+            const Button = () => h("button", null);
+        "#,
+        /* Output */
+        r#"
+            /** @jsx h */
+            const { h } = require('preact');
+            const Button = () => h("button", null);
+            Button.displayName = "Button";
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ jsx_import_source_pragma_in_script,
+        /* Input */
+        r#"
+            /** @jsxImportSource preact */
+            const { jsx: j } = require('preact/jsx-runtime');
+            const Button = () => j("button", {});
+        "#,
+        /* Output */
+        r#"
+            /** @jsxImportSource preact */
+            const { jsx: j } = require('preact/jsx-runtime');
+            const Button = () => j("button", {});
+            Button.displayName = "Button";
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ should_not_work_on_h_without_pragma,
+        /* Input */ r#"
+            export const render = () => h("button", null);
+        "#,
+        /* Output */ r#"
+            export const render = () => h("button", null);
+        "#
+    );
+
+    // CommonJS output
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ commonjs_jsx_runtime_calls,
+        /* Input */
+        r#"
+            const Button = () => (0, _jsxRuntime.jsx)("button", {});
+            const Card = function() { return _react.default.createElement("div", null); };
+        "#,
+        /* Output */
+        r#"
+            const Button = () => (0, _jsxRuntime.jsx)("button", {});
+            Button.displayName = "Button";
+            const Card = function() { return _react.default.createElement("div", null); };
+            Card.displayName = "Card";
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ should_not_work_on_nested_create_element,
+        /* Input */
+        r#"
+            const createNode = () => document.createElement("div");
+            const createOther = () => (0, document.createElement)("div");
+            const createNested = () => x.y.createElement("div");
+        "#,
+        /* Output */
+        r#"
+            const createNode = () => document.createElement("div");
+            const createOther = () => (0, document.createElement)("div");
+            const createNested = () => x.y.createElement("div");
+        "#
+    );
+
+    // Compound components
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ compound_component_assignments,
+        /* Input */
+        r#"
+            import { forwardRef } from 'react';
+            export const Card = () => <div />;
+            Card.Header = () => <header />;
+            Card.Header.Title = function() { return <h1 />; };
+            Menu.Item = forwardRef((props, ref) => <li ref={ref} />);
+            Card.defaultProps = {};
+        "#,
+        /* Output */
+        r#"
+            import { forwardRef } from 'react';
+            export const Card = () => <div />;
+            Card.displayName = "Card";
+            Card.Header = () => <header />;
+            Card.Header.displayName = "Card.Header";
+            Card.Header.Title = function() { return <h1 />; };
+            Card.Header.Title.displayName = "Card.Header.Title";
+            Menu.Item = forwardRef((props, ref) => <li ref={ref} />);
+            Menu.Item.displayName = "Menu.Item";
+            Card.defaultProps = {};
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ compound_component_object_assign,
+        /* Input */
+        r#"
+            const CardRoot = () => <div />;
+            const CardBody = () => <div />;
+            export const Card = Object.assign(CardRoot, { Body: CardBody, Footer: () => <footer /> });
+            Object.assign(Tabs, { Panel, config: {} });
+        "#,
+        /* Output */
+        r#"
+            const CardRoot = () => <div />;
+            CardRoot.displayName = "CardRoot";
+            const CardBody = () => <div />;
+            CardBody.displayName = "CardBody";
+            export const Card = Object.assign(CardRoot, { Body: CardBody, Footer: () => <footer /> });
+            Card.displayName = "Card";
+            Card.Body.displayName = "Card.Body";
+            Card.Footer.displayName = "Card.Footer";
+            Object.assign(Tabs, { Panel, config: {} });
+            Tabs.Panel.displayName = "Tabs.Panel";
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ should_not_work_on_compound_component_with_display_name,
+        /* Input */
+        r#"
+            Card.Header = () => <header />;
+            Card.Header.displayName = "CardHeader";
+        "#,
+        /* Output */
+        r#"
+            Card.Header = () => <header />;
+            Card.Header.displayName = "CardHeader";
+        "#
+    );
+
+    // Object literals
+
+    test_inline!(
+        SYNTAX,
+        |t| runner_with(t, r#"{ "objectLiteralDepth": 2 }"#),
+        /* Name */ object_literal_components,
+        /* Input */
+        r#"
+            export const Icons = {
+                Add: () => <svg />,
+                Arrows: { Left: () => <svg />, Right: () => <svg />, Deep: { Up: () => <svg /> } },
+                size: 16,
+            };
+            const components = { h1: (props) => <h1 {...props} />, wrapper: Layout };
+        "#,
+        /* Output */
+        r#"
+            export const Icons = {
+                Add: () => <svg />,
+                Arrows: { Left: () => <svg />, Right: () => <svg />, Deep: { Up: () => <svg /> } },
+                size: 16,
+            };
+            Icons.Add.displayName = "Icons.Add";
+            Icons.Arrows.Left.displayName = "Icons.Arrows.Left";
+            Icons.Arrows.Right.displayName = "Icons.Arrows.Right";
+            const components = { h1: (props) => <h1 {...props} />, wrapper: Layout };
+            components.h1.displayName = "components.h1";
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ object_literal_disabled_by_default,
+        /* Input */ r#"
+            export const Icons = { Add: () => <svg /> };
+        "#,
+        /* Output */ r#"
+            export const Icons = { Add: () => <svg /> };
+        "#
+    );
+
+    // Nested scopes
+
+    test_inline!(
+        SYNTAX,
+        |t| runner_with(t, r#"{ "scope": "all" }"#),
+        /* Name */ scope_all,
+        /* Input */
+        r#"
+            if (__DEV__) {
+                const Debug = () => <pre />;
+                mount(Debug);
+            }
+            (function () {
+                function Widget() { return <div />; }
+                window.Widget = Widget;
+            })();
+            describe("Card", () => {
+                const Wrapper = ({ children }) => <Provider>{children}</Provider>;
+                const Named = () => <div />;
+                Named.displayName = "Custom";
+            });
+            export const Card = () => <div />;
+        "#,
+        /* Output */
+        r#"
+            if (__DEV__) {
+                const Debug = () => <pre />;
+                Debug.displayName = "Debug";
+                mount(Debug);
+            }
+            (function () {
+                function Widget() { return <div />; }
+                Widget.displayName = "Widget";
+                window.Widget = Widget;
+            })();
+            describe("Card", () => {
+                const Wrapper = ({ children }) => <Provider>{children}</Provider>;
+                Wrapper.displayName = "Wrapper";
+                const Named = () => <div />;
+                Named.displayName = "Custom";
+            });
+            export const Card = () => <div />;
+            Card.displayName = "Card";
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        |t| runner_with(t, r#"{ "scope": "all" }"#),
+        /* Name */ scope_all_shadowed_bindings,
+        /* Input */
+        r#"
+            const Item = () => <li />;
+            function createList() {
+                const Item = () => <div />;
+                return Item;
+            }
+            Item.displayName = "ListItem";
+        "#,
+        /* Output */
+        r#"
+            const Item = () => <li />;
+            function createList() {
+                const Item = () => <div />;
+                Item.displayName = "Item";
+                return Item;
+            }
+            Item.displayName = "ListItem";
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        |t| runner_with(
+            t,
+            r#"{ "scope": "all", "devGuard": { "condition": "__DEV__", "batch": true } }"#
+        ),
+        /* Name */ scope_all_dev_guard_batch,
+        /* Input */
+        r#"
+            function createList() {
+                const Item = () => <li />;
+                return Item;
+            }
+            export const Card = () => <div />;
+        "#,
+        /* Output */
+        r#"
+            function createList() {
+                const Item = () => <li />;
+                if (__DEV__) {
+                    Item.displayName = "Item";
+                }
+                return Item;
+            }
+            export const Card = () => <div />;
+            if (__DEV__) {
+                Card.displayName = "Card";
+            }
+        "#
+    );
+
+    // CommonJS exports
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ commonjs_exports,
+        /* Input */
+        r#"
+            const { memo } = require('react');
+            exports.Button = () => <button />;
+            module.exports.Card = function () { return <div />; };
+            exports.helper = () => 42;
+        "#,
+        /* Output */
+        r#"
+            const { memo } = require('react');
+            exports.Button = () => <button />;
+            exports.Button.displayName = "Button";
+            module.exports.Card = function () { return <div />; };
+            module.exports.Card.displayName = "Card";
+            exports.helper = () => 42;
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ commonjs_required_component_apis,
+        /* Input */
+        r#"
+            const React = require('react');
+            const { memo, forwardRef: fr } = require('react');
+            const ThemeContext = React.createContext(null);
+            const Card = React.memo(Base);
+            const Input = fr(renderInput);
+            module.exports.Button = memo(Base);
+        "#,
+        /* Output */
+        r#"
+            const React = require('react');
+            const { memo, forwardRef: fr } = require('react');
+            const ThemeContext = React.createContext(null);
+            ThemeContext.displayName = "ThemeContext";
+            const Card = React.memo(Base);
+            Card.displayName = "Card";
+            const Input = fr(renderInput);
+            Input.displayName = "Input";
+            module.exports.Button = memo(Base);
+            module.exports.Button.displayName = "Button";
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ commonjs_interop_require,
+        /* Input */
+        r#"
+            var _react = _interopRequireDefault(require("react"));
+            const Card = _react.default.memo(Base);
+        "#,
+        /* Output */
+        r#"
+            var _react = _interopRequireDefault(require("react"));
+            const Card = _react.default.memo(Base);
+            Card.displayName = "Card";
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ should_not_work_on_unknown_required_apis,
+        /* Input */
+        r#"
+            const { memo } = require('some-cache');
+            const cached = memo(compute);
+        "#,
+        /* Output */
+        r#"
+            const { memo } = require('some-cache');
+            const cached = memo(compute);
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ commonjs_module_exports_function,
+        /* Input */
+        r#"
+            module.exports = function Layout() { return <main />; };
+        "#,
+        /* Output */
+        r#"
+            module.exports = function Layout() { return <main />; };
+            module.exports.displayName = "Layout";
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ should_not_work_on_named_commonjs_exports,
+        /* Input */
+        r#"
+            exports.Button = () => <button />;
+            exports.Button.displayName = "MyButton";
+        "#,
+        /* Output */
+        r#"
+            exports.Button = () => <button />;
+            exports.Button.displayName = "MyButton";
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        |t| runner_for_file(t, Some("/app/src/Page.js"), "{}"),
+        /* Name */ commonjs_module_exports_named_after_file,
+        /* Input */
+        r#"
+            module.exports = () => <main />;
+        "#,
+        /* Output */
+        r#"
+            module.exports = () => <main />;
+            module.exports.displayName = "Page";
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        |t| runner_for_file(t, Some("/app/src/Page.js"), "{}"),
+        /* Name */ commonjs_exports_default,
+        /* Input */
+        r#"
+            exports.default = function () { return <main />; };
+        "#,
+        /* Output */
+        r#"
+            exports.default = function () { return <main />; };
+            exports.default.displayName = "Page";
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ should_not_work_on_anonymous_commonjs_default_exports_without_file,
+        /* Input */
+        r#"
+            exports.default = function () { return <main />; };
+            module.exports = () => <div />;
+        "#,
+        /* Output */
+        r#"
+            exports.default = function () { return <main />; };
+            module.exports = () => <div />;
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        |t| runner_for_file(t, Some("/app/src/Page.tsx"), "{}"),
+        /* Name */ typescript_export_assignment,
+        /* Input */ r#"
+            export = () => <main />;
+        "#,
+        /* Output */
+        r#"
+            const Page = () => <main />;
+            Page.displayName = "Page";
+            export = Page;
+        "#
+    );
+
+    // TypeScript expression wrappers
+
+    #[test]
+    fn non_null_assertion_wrapper() {
+        // `() => <li />!` can't be parsed back, so the output isn't compared as text
+        let output = transform("export const Tab = (() => <li />)!;", "{}");
+        assert!(output.contains(r#"Tab.displayName = "Tab";"#), "{output}");
+    }
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ typescript_expression_wrappers,
+        /* Input */
+        r#"
+            import { forwardRef } from 'react';
+            export const Button = (forwardRef((props, ref) => <button ref={ref} />) as ButtonComponent);
+            export const Card = (() => <div />) satisfies FC;
+            export const Paren = (() => <div />);
+        "#,
+        // The harness prints the output without the parentheses
+        /* Output */
+        r#"
+            import { forwardRef } from 'react';
+            export const Button = forwardRef((props, ref) => <button ref={ref} />) as ButtonComponent;
+            Button.displayName = "Button";
+            export const Card = () => <div /> satisfies FC;
+            Card.displayName = "Card";
+            export const Paren = () => <div />;
+            Paren.displayName = "Paren";
+        "#
+    );
+
+    test_inline!(
+        // Angle-bracket assertions only parse outside of TSX
+        Syntax::Typescript(TsSyntax {
+            tsx: false,
+            ..Default::default()
+        }),
+        runner,
+        /* Name */ typescript_angle_bracket_and_const_assertions,
+        /* Input */
+        r#"
+            import { createElement, memo } from 'react';
+            export const Card = <FC>(() => createElement("div", null));
+            export const Fancy = memo(() => createElement("div", null)) as const;
+        "#,
+        // The harness prints the output without the parentheses
+        /* Output */
+        r#"
+            import { createElement, memo } from 'react';
+            export const Card = <FC>() => createElement("div", null);
+            Card.displayName = "Card";
+            export const Fancy = memo(() => createElement("div", null)) as const;
+            Fancy.displayName = "Fancy";
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ should_not_work_on_wrapped_elements,
+        /* Input */
+        r#"
+            export const icon = (<svg />);
+            export const config = { size: 16 } as const;
+        "#,
+        /* Output */
+        r#"
+            export const icon = <svg />;
+            export const config = { size: 16 } as const;
+        "#
+    );
+
+    // Type annotations
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ typescript_component_types,
+        /* Input */
+        r#"
+            import React, { FunctionComponent } from 'react';
+            export const Empty: React.FC = () => null;
+            export const Slot: FunctionComponent<Props> = ({ children }) => children;
+            export function Render(): JSX.Element { return render(); }
+            export const Maybe = (props: Props): React.ReactElement | null => props.render();
+            export const count: number = 42;
+        "#,
+        /* Output */
+        r#"
+            import React, { FunctionComponent } from 'react';
+            export const Empty: React.FC = () => null;
+            Empty.displayName = "Empty";
+            export const Slot: FunctionComponent<Props> = ({ children }) => children;
+            Slot.displayName = "Slot";
+            export function Render(): JSX.Element { return render(); }
+            Render.displayName = "Render";
+            export const Maybe = (props: Props): React.ReactElement | null => props.render();
+            Maybe.displayName = "Maybe";
+            export const count: number = 42;
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        |t| runner_with(t, r#"{ "componentTypes": ["PolymorphicComponent"] }"#),
+        /* Name */ custom_component_types,
+        /* Input */
+        r#"
+            export const Box: PolymorphicComponent<"div"> = createBox();
+            export const theme: Theme = createTheme();
+        "#,
+        /* Output */
+        r#"
+            export const Box: PolymorphicComponent<"div"> = createBox();
+            Box.displayName = "Box";
+            export const theme: Theme = createTheme();
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ should_not_work_on_uninitialized_component_types,
+        /* Input */
+        r#"
+            import React from 'react';
+            let Current: React.ComponentType;
+            Current = pickComponent();
+        "#,
+        /* Output */
+        r#"
+            import React from 'react';
+            let Current: React.ComponentType;
+            Current = pickComponent();
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ should_not_work_on_ambient_declarations,
+        /* Input */
+        r#"
+            import { FC } from 'react';
+            declare const Ext: FC;
+            export declare const Legacy: FC;
+            declare function Render(): JSX.Element;
+        "#,
+        /* Output */
+        r#"
+            import { FC } from 'react';
+            declare const Ext: FC;
+            export declare const Legacy: FC;
+            declare function Render(): JSX.Element;
+        "#
+    );
+
+    // Styled tags
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ styled_tag_chains,
+        /* Input */
+        r#"
+            import styled from '@emotion/styled';
+            export const Primary = styled(Button)`color: blue;`;
+            export const Box = styled("div")`display: flex;`;
+            export const Input = styled.input.attrs({ type: "text" })`border: 0;`;
+            export const Link = styled.a.withConfig({ shouldForwardProp })`color: red;`;
+            export const rule = css`color: red;`;
+        "#,
+        /* Output */
+        r#"
+            import styled from '@emotion/styled';
+            export const Primary = styled(Button)`color: blue;`;
+            Primary.displayName = "Primary";
+            export const Box = styled("div")`display: flex;`;
+            Box.displayName = "Box";
+            export const Input = styled.input.attrs({ type: "text" })`border: 0;`;
+            Input.displayName = "Input";
+            export const Link = styled.a.withConfig({ shouldForwardProp })`color: red;`;
+            Link.displayName = "Link";
+            export const rule = css`color: red;`;
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        runner,
+        /* Name */ should_not_work_on_styled_from_other_sources,
+        /* Input */
+        r#"
+            import { styled } from './theme';
+            export const Primary = styled(Button)`color: blue;`;
+            export const Box = styled.div`display: flex;`;
+        "#,
+        /* Output */
+        r#"
+            import { styled } from './theme';
+            export const Primary = styled(Button)`color: blue;`;
+            export const Box = styled.div`display: flex;`;
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        |t| runner_with(t, r#"{ "styledTags": ["glamorous"] }"#),
+        /* Name */ custom_styled_tags,
+        /* Input */
+        r#"
+            export const Title = glamorous.h1`font-size: 2em;`;
+            export const Plain = styled.h1`font-size: 2em;`;
+        "#,
+        /* Output */
+        r#"
+            export const Title = glamorous.h1`font-size: 2em;`;
+            Title.displayName = "Title";
+            export const Plain = styled.h1`font-size: 2em;`;
+        "#
+    );
+
+    test_inline!(
+        SYNTAX,
+        |t| runner_with(
+            t,
+            r#"{
+                "styledTags": [
+                    "styled",
+                    { "name": "tw", "source": "tailwind-styled-components" },
+                    { "name": "tw", "source": "twin.macro" },
+                    { "name": "styled", "source": "@linaria/react" }
+                ]
+            }"#
+        ),
+        /* Name */ styled_tags_with_import_sources,
+        /* Input */
+        r#"
+            import tw from 'tailwind-styled-components';
+            import { styled as linaria } from '@linaria/react';
+            import { tw as other } from './tw';
+            export const Button = tw.button`px-4 py-2`;
+            export const Card = linaria.div`padding: 8px;`;
+            export const Title = styled.h1`font-size: 2em;`;
+            export const Plain = other.div`p-4`;
+        "#,
+        /* Output */
+        r#"
+            import tw from 'tailwind-styled-components';
+            import { styled as linaria } from '@linaria/react';
+            import { tw as other } from './tw';
+            export const Button = tw.button`px-4 py-2`;
+            Button.displayName = "Button";
+            export const Card = linaria.div`padding: 8px;`;
+            Card.displayName = "Card";
+            export const Title = styled.h1`font-size: 2em;`;
+            Title.displayName = "Title";
+            export const Plain = other.div`p-4`;
+        "#
+    );
 }